@@ -3,9 +3,13 @@ import wasm from "../dist/wasm.js";
 const {
     initThreadPool: wasmInitThreadPool,
     Address,
+    BalanceExtractor,
+    Devnet,
     Execution,
     ExecutionResponse,
     Field,
+    GraphKey,
+    MappingStore,
     Metadata,
     OfflineQuery,
     Private,
@@ -40,9 +44,13 @@ async function initThreadPool(threads) {
 export {
     initThreadPool,
     Address,
+    BalanceExtractor,
+    Devnet,
     Execution,
     ExecutionResponse,
     Field,
+    GraphKey,
+    MappingStore,
     Metadata,
     OfflineQuery,
     PrivateKey,
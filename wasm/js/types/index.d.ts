@@ -7,9 +7,13 @@ export function initThreadPool(threads?: number): Promise<void>;
 
 export {
     Address,
+    BalanceExtractor,
+    Devnet,
     Execution,
     ExecutionResponse,
     Field,
+    GraphKey,
+    MappingStore,
     Metadata,
     OfflineQuery,
     PrivateKey,
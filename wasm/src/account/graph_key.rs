@@ -0,0 +1,100 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{PrivateKey, ViewKey};
+use crate::types::Field;
+
+use crate::types::native::GraphKeyNative;
+use core::{convert::TryFrom, ops::Deref};
+use wasm_bindgen::prelude::*;
+
+/// Graph key of an Aleo account. The graph key is derived from the view key and can be used to
+/// compute the tags of records owned by the account, which allows a view key holder to determine
+/// whether a record has been spent without access to the private key.
+#[wasm_bindgen]
+#[derive(Clone, PartialEq, Eq)]
+pub struct GraphKey(GraphKeyNative);
+
+#[wasm_bindgen]
+impl GraphKey {
+    /// Derive a graph key from a view key
+    ///
+    /// @param {ViewKey} view_key View key to derive the graph key from
+    /// @returns {GraphKey | Error} Graph key
+    #[wasm_bindgen(js_name = "fromViewKey")]
+    pub fn from_view_key(view_key: &ViewKey) -> Result<GraphKey, String> {
+        Ok(Self(GraphKeyNative::try_from(**view_key).map_err(|_| "Graph key derivation failed".to_string())?))
+    }
+
+    /// Derive a graph key from a private key
+    ///
+    /// @param {PrivateKey} private_key Private key to derive the graph key from
+    /// @returns {GraphKey | Error} Graph key
+    #[wasm_bindgen(js_name = "fromPrivateKey")]
+    pub fn from_private_key(private_key: &PrivateKey) -> Result<GraphKey, String> {
+        Self::from_view_key(&private_key.to_view_key())
+    }
+
+    /// Get the tag secret key of the graph key
+    ///
+    /// @returns {Field} The tag secret key
+    #[wasm_bindgen(js_name = "skTag")]
+    pub fn sk_tag(&self) -> Field {
+        Field::from(self.0.sk_tag())
+    }
+}
+
+impl From<GraphKeyNative> for GraphKey {
+    fn from(value: GraphKeyNative) -> Self {
+        Self(value)
+    }
+}
+
+impl From<GraphKey> for GraphKeyNative {
+    fn from(graph_key: GraphKey) -> Self {
+        graph_key.0
+    }
+}
+
+impl Deref for GraphKey {
+    type Target = GraphKeyNative;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wasm_bindgen_test::*;
+
+    const ITERATIONS: u64 = 100;
+
+    #[wasm_bindgen_test]
+    pub fn test_from_private_key() {
+        for _ in 0..ITERATIONS {
+            // Sample a new private key.
+            let private_key = PrivateKey::new();
+            let expected = GraphKey::from_private_key(&private_key).unwrap();
+
+            // Check the graph key derived from the view key.
+            let view_key = private_key.to_view_key();
+            assert!(expected == GraphKey::from_view_key(&view_key).unwrap());
+        }
+    }
+}
@@ -20,6 +20,9 @@ pub use address::*;
 pub mod encryptor;
 pub use encryptor::*;
 
+pub mod graph_key;
+pub use graph_key::*;
+
 pub mod private_key;
 pub use private_key::*;
 
@@ -221,6 +221,8 @@ struct TransferInfo {
     receiver: String,
     amount: u64,
     fee: u64,
+    #[serde(default)]
+    priority_fee: u64,
     state_root: String,
     transfer_proving_key: String,
     transfer_verifying_key: String,
@@ -228,7 +230,8 @@ struct TransferInfo {
     fee_verifying_key: String,
 }
 
-/// A trait providing convenient methods for accessing the amount of Aleo present in a record
+/// A trait providing convenient methods for accessing the amount of Aleo credits or other tokens
+/// present in a record
 pub trait Credits {
     /// Get the amount of credits in the record if the record possesses Aleo credits
     fn credits(&self) -> Result<f64, String> {
@@ -236,20 +239,18 @@ pub trait Credits {
     }
 
     /// Get the amount of microcredits in the record if the record possesses Aleo credits
-    fn microcredits(&self) -> Result<u64, String>;
+    fn microcredits(&self) -> Result<u64, String> {
+        u64::try_from(self.balance(&BalanceExtractor::credits())?)
+            .map_err(|_| "The microcredits field of the record exceeds the u64 range".to_string())
+    }
+
+    /// Get the token balance of the record as described by a balance extractor
+    fn balance(&self, extractor: &BalanceExtractor) -> Result<u128, String>;
 }
 
 impl Credits for RecordPlaintextNative {
-    fn microcredits(&self) -> Result<u64, String> {
-        match self
-            .find(&[native::IdentifierNative::from_str("microcredits").map_err(|e| e.to_string())?])
-            .map_err(|e| e.to_string())?
-        {
-            native::Entry::Private(native::PlaintextNative::Literal(native::LiteralNative::U64(amount), _)) => {
-                Ok(*amount)
-            }
-            _ => Err("The record provided does not contain a microcredits field".to_string()),
-        }
+    fn balance(&self, extractor: &BalanceExtractor) -> Result<u128, String> {
+        extractor.extract(self)
     }
 }
 
@@ -414,7 +415,7 @@ fn create_transfer(data: TransferInfo) -> Result<Transaction, String>{
          // base fee
          data.fee,
          //priority fee
-         0u64,
+         data.priority_fee,
          execution_id,
          rng,
      ).map_err(|e| e.to_string())?;
@@ -0,0 +1,337 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::types::native::{
+    Command,
+    CurrentNetwork,
+    EntryType,
+    FinalizeType,
+    PlaintextType,
+    ProgramNative,
+    RegisterType,
+    ValueType,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The version of the ABI format. This is incremented whenever the layout of the ABI changes in
+/// a way that is not backwards compatible.
+pub const ABI_VERSION: u32 = 1;
+
+/// A complete description of the interface of an Aleo program
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramAbi {
+    pub version: u32,
+    pub program: String,
+    pub imports: Vec<String>,
+    pub structs: Vec<StructAbi>,
+    pub records: Vec<RecordAbi>,
+    pub mappings: Vec<MappingAbi>,
+    pub closures: Vec<ClosureAbi>,
+    pub functions: Vec<FunctionAbi>,
+}
+
+/// The type of a plaintext value
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlaintextTypeAbi {
+    Literal { r#type: String },
+    Struct { name: String },
+    Array { element: Box<PlaintextTypeAbi>, length: u32 },
+}
+
+/// The type of a function input or output, including its visibility
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ValueTypeAbi {
+    Constant { r#type: PlaintextTypeAbi },
+    Public { r#type: PlaintextTypeAbi },
+    Private { r#type: PlaintextTypeAbi },
+    Record { record: String },
+    ExternalRecord { locator: String },
+    Future { locator: String },
+}
+
+/// The type of a closure input or output or of a finalize input
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum RegisterTypeAbi {
+    Plaintext { r#type: PlaintextTypeAbi },
+    Record { record: String },
+    ExternalRecord { locator: String },
+    Future { locator: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StructAbi {
+    pub name: String,
+    pub members: Vec<MemberAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberAbi {
+    pub name: String,
+    pub r#type: PlaintextTypeAbi,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordAbi {
+    pub name: String,
+    pub owner: String,
+    pub entries: Vec<RecordEntryAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordEntryAbi {
+    pub name: String,
+    pub visibility: String,
+    pub r#type: PlaintextTypeAbi,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappingAbi {
+    pub name: String,
+    pub key: PlaintextTypeAbi,
+    pub value: PlaintextTypeAbi,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClosureAbi {
+    pub name: String,
+    pub inputs: Vec<RegisterAbi>,
+    pub outputs: Vec<RegisterTypeAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegisterAbi {
+    pub register: String,
+    pub r#type: RegisterTypeAbi,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionAbi {
+    pub name: String,
+    pub inputs: Vec<InputAbi>,
+    pub outputs: Vec<ValueTypeAbi>,
+    pub finalize: Option<FinalizeAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputAbi {
+    pub register: String,
+    pub r#type: ValueTypeAbi,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinalizeAbi {
+    pub inputs: Vec<RegisterAbi>,
+    /// Mappings read by the finalize block, qualified with their program name if external
+    pub reads: Vec<String>,
+    /// Mappings written to by the finalize block
+    pub writes: Vec<String>,
+}
+
+impl ProgramAbi {
+    /// Build the ABI of a program
+    pub fn from_program(program: &ProgramNative) -> Self {
+        let structs = program
+            .structs()
+            .iter()
+            .map(|(name, struct_type)| StructAbi {
+                name: name.to_string(),
+                members: struct_type
+                    .members()
+                    .iter()
+                    .map(|(name, member_type)| MemberAbi {
+                        name: name.to_string(),
+                        r#type: PlaintextTypeAbi::from(member_type),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let records = program
+            .records()
+            .iter()
+            .map(|(name, record_type)| RecordAbi {
+                name: name.to_string(),
+                owner: if record_type.owner().is_public() { "public" } else { "private" }.to_string(),
+                entries: record_type
+                    .entries()
+                    .iter()
+                    .map(|(name, entry_type)| {
+                        let (visibility, plaintext_type) = match entry_type {
+                            EntryType::Constant(plaintext_type) => ("constant", plaintext_type),
+                            EntryType::Public(plaintext_type) => ("public", plaintext_type),
+                            EntryType::Private(plaintext_type) => ("private", plaintext_type),
+                        };
+                        RecordEntryAbi {
+                            name: name.to_string(),
+                            visibility: visibility.to_string(),
+                            r#type: PlaintextTypeAbi::from(plaintext_type),
+                        }
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mappings = program
+            .mappings()
+            .iter()
+            .map(|(name, mapping)| MappingAbi {
+                name: name.to_string(),
+                key: PlaintextTypeAbi::from(mapping.key().plaintext_type()),
+                value: PlaintextTypeAbi::from(mapping.value().plaintext_type()),
+            })
+            .collect();
+
+        let closures = program
+            .closures()
+            .iter()
+            .map(|(name, closure)| ClosureAbi {
+                name: name.to_string(),
+                inputs: closure
+                    .inputs()
+                    .iter()
+                    .map(|input| RegisterAbi {
+                        register: input.register().to_string(),
+                        r#type: RegisterTypeAbi::from(input.register_type()),
+                    })
+                    .collect(),
+                outputs: closure.outputs().iter().map(|output| RegisterTypeAbi::from(output.register_type())).collect(),
+            })
+            .collect();
+
+        let functions = program
+            .functions()
+            .iter()
+            .map(|(name, function)| FunctionAbi {
+                name: name.to_string(),
+                inputs: function
+                    .inputs()
+                    .iter()
+                    .map(|input| InputAbi {
+                        register: input.register().to_string(),
+                        r#type: ValueTypeAbi::from(input.value_type()),
+                    })
+                    .collect(),
+                outputs: function.outputs().iter().map(|output| ValueTypeAbi::from(output.value_type())).collect(),
+                finalize: function.finalize_logic().map(|finalize| {
+                    let mut reads = Vec::new();
+                    let mut writes = Vec::new();
+                    for command in finalize.commands() {
+                        let (mapping, is_write) = match command {
+                            Command::Contains(contains) => (contains.mapping().to_string(), false),
+                            Command::Get(get) => (get.mapping().to_string(), false),
+                            Command::GetOrUse(get_or_use) => (get_or_use.mapping().to_string(), false),
+                            Command::Set(set) => (set.mapping_name().to_string(), true),
+                            Command::Remove(remove) => (remove.mapping_name().to_string(), true),
+                            _ => continue,
+                        };
+                        let accesses = if is_write { &mut writes } else { &mut reads };
+                        if !accesses.contains(&mapping) {
+                            accesses.push(mapping);
+                        }
+                    }
+                    FinalizeAbi {
+                        inputs: finalize
+                            .inputs()
+                            .iter()
+                            .map(|input| RegisterAbi {
+                                register: input.register().to_string(),
+                                r#type: RegisterTypeAbi::from(input.finalize_type()),
+                            })
+                            .collect(),
+                        reads,
+                        writes,
+                    }
+                }),
+            })
+            .collect();
+
+        Self {
+            version: ABI_VERSION,
+            program: program.id().to_string(),
+            imports: program.imports().keys().map(|import| import.to_string()).collect(),
+            structs,
+            records,
+            mappings,
+            closures,
+            functions,
+        }
+    }
+
+    /// Get a function of the ABI by name
+    pub fn function(&self, name: &str) -> Option<&FunctionAbi> {
+        self.functions.iter().find(|function| function.name == name)
+    }
+
+    /// Get a struct of the ABI by name
+    pub fn get_struct(&self, name: &str) -> Option<&StructAbi> {
+        self.structs.iter().find(|struct_abi| struct_abi.name == name)
+    }
+
+    /// Get a record of the ABI by name
+    pub fn record(&self, name: &str) -> Option<&RecordAbi> {
+        self.records.iter().find(|record| record.name == name)
+    }
+}
+
+impl From<&PlaintextType<CurrentNetwork>> for PlaintextTypeAbi {
+    fn from(plaintext_type: &PlaintextType<CurrentNetwork>) -> Self {
+        match plaintext_type {
+            PlaintextType::Literal(literal_type) => Self::Literal { r#type: literal_type.to_string() },
+            PlaintextType::Struct(struct_id) => Self::Struct { name: struct_id.to_string() },
+            PlaintextType::Array(array_type) => Self::Array {
+                element: Box::new(Self::from(array_type.next_element_type())),
+                length: **array_type.length(),
+            },
+        }
+    }
+}
+
+impl From<&ValueType<CurrentNetwork>> for ValueTypeAbi {
+    fn from(value_type: &ValueType<CurrentNetwork>) -> Self {
+        match value_type {
+            ValueType::Constant(plaintext_type) => Self::Constant { r#type: plaintext_type.into() },
+            ValueType::Public(plaintext_type) => Self::Public { r#type: plaintext_type.into() },
+            ValueType::Private(plaintext_type) => Self::Private { r#type: plaintext_type.into() },
+            ValueType::Record(record) => Self::Record { record: record.to_string() },
+            ValueType::ExternalRecord(locator) => Self::ExternalRecord { locator: locator.to_string() },
+            ValueType::Future(locator) => Self::Future { locator: locator.to_string() },
+        }
+    }
+}
+
+impl From<&RegisterType<CurrentNetwork>> for RegisterTypeAbi {
+    fn from(register_type: &RegisterType<CurrentNetwork>) -> Self {
+        match register_type {
+            RegisterType::Plaintext(plaintext_type) => Self::Plaintext { r#type: plaintext_type.into() },
+            RegisterType::Record(record) => Self::Record { record: record.to_string() },
+            RegisterType::ExternalRecord(locator) => Self::ExternalRecord { locator: locator.to_string() },
+            RegisterType::Future(locator) => Self::Future { locator: locator.to_string() },
+        }
+    }
+}
+
+impl From<&FinalizeType<CurrentNetwork>> for RegisterTypeAbi {
+    fn from(finalize_type: &FinalizeType<CurrentNetwork>) -> Self {
+        match finalize_type {
+            FinalizeType::Plaintext(plaintext_type) => Self::Plaintext { r#type: plaintext_type.into() },
+            FinalizeType::Future(locator) => Self::Future { locator: locator.to_string() },
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::log;
+
+use js_sys::BigInt;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// The number of microcredits in one credit
+pub const MICROCREDITS_PER_CREDIT: u64 = 1_000_000;
+
+// The number of decimal places of a credit amount
+const CREDIT_DECIMALS: usize = 6;
+
+// The largest integer a javascript number can represent exactly
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Parse a decimal amount of credits such as `"1"`, `"0.3"` or `"12.000001"` into microcredits.
+///
+/// Parsing is strict: the amount must consist of digits with an optional fractional part of at
+/// most 6 digits. Signs, exponents, whitespace and amounts above the u64 range are rejected rather
+/// than rounded.
+pub fn parse_credits(credits: &str) -> Result<u64, String> {
+    let invalid = || format!("'{credits}' is not a valid amount of credits");
+
+    let (integer, fraction) = match credits.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (credits, ""),
+    };
+    if integer.is_empty()
+        || !integer.bytes().all(|byte| byte.is_ascii_digit())
+        || !fraction.bytes().all(|byte| byte.is_ascii_digit())
+        || (credits.contains('.') && fraction.is_empty())
+    {
+        return Err(invalid());
+    }
+    if fraction.len() > CREDIT_DECIMALS {
+        return Err(format!("'{credits}' has more than {CREDIT_DECIMALS} decimal places"));
+    }
+
+    let integer = integer.parse::<u64>().map_err(|_| invalid())?;
+    let fraction = format!("{fraction:0<width$}", width = CREDIT_DECIMALS).parse::<u64>().map_err(|_| invalid())?;
+    integer
+        .checked_mul(MICROCREDITS_PER_CREDIT)
+        .and_then(|microcredits| microcredits.checked_add(fraction))
+        .ok_or_else(|| format!("'{credits}' exceeds the maximum amount of microcredits"))
+}
+
+/// Convert a javascript amount into an exact number of microcredits.
+///
+/// Bigints are interpreted as microcredits and strings as decimal amounts of credits parsed with
+/// [`parse_credits`]. Numbers are interpreted as credits and are only accepted for backwards
+/// compatibility, with a deprecation warning: they are converted through their shortest decimal
+/// representation, so amounts which cannot be expressed exactly in microcredits are rejected
+/// instead of truncated.
+pub fn to_microcredits(value: &JsValue) -> Result<u64, String> {
+    if value.is_bigint() {
+        let bigint = value.unchecked_ref::<BigInt>();
+        let microcredits =
+            String::from(bigint.to_string(10).map_err(|_| "failed to convert the bigint to a string".to_string())?);
+        microcredits.parse::<u64>().map_err(|_| format!("{microcredits} is not a valid amount of microcredits"))
+    } else if let Some(credits) = value.as_string() {
+        parse_credits(&credits)
+    } else if let Some(credits) = value.as_f64() {
+        log(&format!(
+            "Passing {credits} credits as a number is deprecated, pass a bigint of microcredits or a decimal string of credits instead"
+        ));
+        if credits.is_finite() && credits * MICROCREDITS_PER_CREDIT as f64 > MAX_SAFE_INTEGER {
+            return Err(format!("{credits} credits cannot be represented exactly, use a bigint or a string instead"));
+        }
+        parse_credits(&credits.to_string())
+    } else {
+        Err("Amounts must be a bigint of microcredits, a decimal string of credits or a number".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn test_parse_credits() {
+        assert_eq!(parse_credits("1").unwrap(), 1_000_000);
+        assert_eq!(parse_credits("0.3").unwrap(), 300_000);
+        assert_eq!(parse_credits("12.000001").unwrap(), 12_000_001);
+        assert_eq!(parse_credits("18446744073709.551615").unwrap(), u64::MAX);
+
+        assert!(parse_credits("").is_err());
+        assert!(parse_credits(".5").is_err());
+        assert!(parse_credits("1.").is_err());
+        assert!(parse_credits("-1").is_err());
+        assert!(parse_credits("1e6").is_err());
+        assert!(parse_credits(" 1").is_err());
+        assert!(parse_credits("0.0000001").is_err());
+        assert!(parse_credits("18446744073709.551616").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_to_microcredits() {
+        assert_eq!(to_microcredits(&JsValue::from(0.3)).unwrap(), 300_000);
+        assert_eq!(to_microcredits(&JsValue::from_str("0.3")).unwrap(), 300_000);
+        assert_eq!(to_microcredits(&JsValue::from(BigInt::from(u64::MAX))).unwrap(), u64::MAX);
+
+        assert!(to_microcredits(&JsValue::from(0.1 + 0.2)).is_err());
+        assert!(to_microcredits(&JsValue::from(-1.0)).is_err());
+        assert!(to_microcredits(&JsValue::from(f64::NAN)).is_err());
+        assert!(to_microcredits(&JsValue::from(1e10)).is_err());
+        assert!(to_microcredits(&JsValue::from(BigInt::from(-1i64))).is_err());
+        assert!(to_microcredits(&JsValue::TRUE).is_err());
+    }
+}
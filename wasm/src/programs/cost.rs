@@ -0,0 +1,292 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::types::native::{
+    cost_in_microcredits,
+    deployment_cost,
+    CurrentNetwork,
+    DeploymentNative,
+    ExecutionNative,
+    FeeNative,
+    ProcessNative,
+    ToBytes,
+};
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::JsValue;
+
+/// The size of the circuit of a function and the share of the synthesis cost it accounts for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionCircuitCost {
+    pub function: String,
+    pub constraints: u64,
+    pub variables: u64,
+    pub synthesis_cost: u64,
+}
+
+/// An itemized breakdown of the cost of a deployment in microcredits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeploymentCostBreakdown {
+    /// The size of the deployment in bytes
+    pub storage_bytes: u64,
+    /// The cost of storing the deployment
+    pub storage_cost: u64,
+    /// The cost of synthesizing the circuits of every function of the program
+    pub synthesis_cost: u64,
+    /// The cost of the program name
+    pub namespace_cost: u64,
+    /// The total cost of the deployment
+    pub total_cost: u64,
+    /// The circuit size and synthesis cost of each function
+    pub functions: Vec<FunctionCircuitCost>,
+}
+
+impl DeploymentCostBreakdown {
+    /// Compute the cost breakdown of a deployment
+    pub fn from_deployment(deployment: &DeploymentNative) -> Result<Self, String> {
+        let (total_cost, (storage_cost, synthesis_cost, namespace_cost)) =
+            deployment_cost::<CurrentNetwork>(deployment).map_err(|e| e.to_string())?;
+        let storage_bytes = deployment.size_in_bytes().map_err(|e| e.to_string())?;
+
+        // The synthesis cost is linear in the number of constraints and variables of the circuits,
+        // so each function accounts for its share of the combined circuit size.
+        let sizes = deployment
+            .verifying_keys()
+            .iter()
+            .map(|(function, (verifying_key, _))| {
+                (function.to_string(), verifying_key.circuit_info.num_constraints as u64, verifying_key.num_variables())
+            })
+            .collect::<Vec<_>>();
+        let combined_size =
+            sizes.iter().map(|(_, constraints, variables)| u128::from(constraints + variables)).sum::<u128>();
+        let functions = sizes
+            .into_iter()
+            .map(|(function, constraints, variables)| {
+                let share = match combined_size {
+                    0 => 0,
+                    _ => u128::from(synthesis_cost) * u128::from(constraints + variables) / combined_size,
+                };
+                FunctionCircuitCost { function, constraints, variables, synthesis_cost: share as u64 }
+            })
+            .collect();
+
+        Ok(Self { storage_bytes, storage_cost, synthesis_cost, namespace_cost, total_cost, functions })
+    }
+
+    /// Convert the breakdown into a javascript object of the form
+    /// `{ storageBytes, storageCost, synthesisCost, namespaceCost, totalCost, functions }` where
+    /// each function is of the form `{ function, constraints, variables, synthesisCost }`
+    pub fn to_object(&self) -> Result<Object, String> {
+        let functions = Array::new_with_length(self.functions.len() as u32);
+        for (index, function) in self.functions.iter().enumerate() {
+            let object = Object::new();
+            Reflect::set(&object, &"function".into(), &function.function.clone().into())
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&object, &"constraints".into(), &JsValue::from(function.constraints as f64))
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&object, &"variables".into(), &JsValue::from(function.variables as f64))
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&object, &"synthesisCost".into(), &JsValue::from(function.synthesis_cost as f64))
+                .map_err(|_| "Failed to set property")?;
+            functions.set(index as u32, object.into());
+        }
+        let object = Object::new();
+        Reflect::set(&object, &"storageBytes".into(), &JsValue::from(self.storage_bytes as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"storageCost".into(), &JsValue::from(self.storage_cost as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"synthesisCost".into(), &JsValue::from(self.synthesis_cost as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"namespaceCost".into(), &JsValue::from(self.namespace_cost as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"totalCost".into(), &JsValue::from(self.total_cost as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"functions".into(), &functions.into()).map_err(|_| "Failed to set property")?;
+        Ok(object)
+    }
+}
+
+// The proof size constants below are conservative approximations of the size of a batch proof,
+// chosen so the estimate errs on the side of a higher fee. `test_fee_estimation` checks that the
+// estimated size of an execution is never below the size of the proven execution and exceeds it
+// by at most 20%.
+
+/// The approximate size in bytes of the proof of a single instance of a single circuit
+pub const ESTIMATED_PROOF_BASE_BYTES: u64 = 1_000;
+
+/// The approximate number of bytes each additional circuit, together with its first instance,
+/// adds to a proof
+pub const ESTIMATED_PROOF_BYTES_PER_CIRCUIT: u64 = 600;
+
+/// The approximate number of bytes each additional instance of a circuit adds to a proof
+pub const ESTIMATED_PROOF_BYTES_PER_INSTANCE: u64 = 250;
+
+/// Approximate the size in bytes of the proof of an execution from its transitions. The proof
+/// batches one circuit per distinct function, plus the inclusion circuit if the execution consumes
+/// records, and one instance per transition and per consumed record.
+pub fn estimate_proof_size(execution: &ExecutionNative) -> u64 {
+    let mut circuits = std::collections::HashSet::new();
+    let mut instances = 0u64;
+    let mut records = 0u64;
+    for transition in execution.transitions() {
+        circuits.insert((transition.program_id().to_string(), transition.function_name().to_string()));
+        instances += 1;
+        records += transition.serial_numbers().count() as u64;
+    }
+    let mut circuits = circuits.len() as u64;
+    if records > 0 {
+        circuits += 1;
+        instances += records;
+    }
+    ESTIMATED_PROOF_BASE_BYTES
+        + circuits.saturating_sub(1) * ESTIMATED_PROOF_BYTES_PER_CIRCUIT
+        + instances.saturating_sub(circuits) * ESTIMATED_PROOF_BYTES_PER_INSTANCE
+}
+
+/// The contribution of a single transition to the cost of an execution
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransitionCost {
+    pub program: String,
+    pub function: String,
+    /// The size of the transition in bytes
+    pub bytes: u64,
+    /// The cost of the finalize logic of the transition
+    pub finalize_cost: u64,
+}
+
+/// An itemized breakdown of the cost of an execution in microcredits
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionCostBreakdown {
+    /// The size of the execution in bytes, which is also its storage cost
+    pub execution_bytes: u64,
+    /// The size of the execution not accounted for by its transitions (the proof and state root)
+    pub proof_bytes: u64,
+    /// The cost of storing the execution
+    pub storage_cost: u64,
+    /// The combined cost of the finalize logic of every transition
+    pub finalize_cost: u64,
+    /// The total cost of the execution
+    pub total_cost: u64,
+    /// The size and finalize cost of each transition, in execution order
+    pub transitions: Vec<TransitionCost>,
+    /// The size of the fee transition in bytes if the fee is known
+    pub fee_bytes: Option<u64>,
+}
+
+impl ExecutionCostBreakdown {
+    /// Compute the cost breakdown of an execution. The programs of the execution must be loaded in
+    /// the process
+    pub fn from_execution(
+        process: &ProcessNative,
+        execution: &ExecutionNative,
+        fee: Option<&FeeNative>,
+    ) -> Result<Self, String> {
+        let execution_bytes = execution.size_in_bytes().map_err(|e| e.to_string())?;
+        Self::itemize(process, execution, execution_bytes, fee)
+    }
+
+    /// Estimate the cost breakdown of an execution that has not been proven. The size of the
+    /// proof is approximated with `estimate_proof_size` and added to the size of the execution.
+    /// The programs of the execution must be loaded in the process
+    pub fn from_unproven_execution(process: &ProcessNative, execution: &ExecutionNative) -> Result<Self, String> {
+        if execution.proof().is_some() {
+            return Self::from_execution(process, execution, None);
+        }
+        let execution_bytes =
+            execution.size_in_bytes().map_err(|e| e.to_string())?.saturating_add(estimate_proof_size(execution));
+        Self::itemize(process, execution, execution_bytes, None)
+    }
+
+    // Itemize the cost of an execution of the given size.
+    fn itemize(
+        process: &ProcessNative,
+        execution: &ExecutionNative,
+        execution_bytes: u64,
+        fee: Option<&FeeNative>,
+    ) -> Result<Self, String> {
+        let mut transitions = Vec::with_capacity(execution.len());
+        for transition in execution.transitions() {
+            let stack = process.get_stack(transition.program_id()).map_err(|e| e.to_string())?;
+            transitions.push(TransitionCost {
+                program: transition.program_id().to_string(),
+                function: transition.function_name().to_string(),
+                bytes: transition.to_bytes_le().map_err(|e| e.to_string())?.len() as u64,
+                finalize_cost: cost_in_microcredits(stack, transition.function_name()).map_err(|e| e.to_string())?,
+            });
+        }
+
+        let finalize_cost = transitions
+            .iter()
+            .try_fold(0u64, |total, transition| total.checked_add(transition.finalize_cost))
+            .ok_or("The finalize cost computation overflowed for an execution".to_string())?;
+        let transition_bytes = transitions.iter().map(|transition| transition.bytes).sum::<u64>();
+        let fee_bytes = match fee {
+            Some(fee) => Some(fee.to_bytes_le().map_err(|e| e.to_string())?.len() as u64),
+            None => None,
+        };
+
+        Ok(Self {
+            execution_bytes,
+            proof_bytes: execution_bytes.saturating_sub(transition_bytes),
+            storage_cost: execution_bytes,
+            finalize_cost,
+            total_cost: execution_bytes
+                .checked_add(finalize_cost)
+                .ok_or("The execution cost computation overflowed".to_string())?,
+            transitions,
+            fee_bytes,
+        })
+    }
+
+    /// Convert the breakdown into a javascript object of the form
+    /// `{ executionBytes, proofBytes, storageCost, finalizeCost, totalCost, feeBytes, transitions }`
+    /// where each transition is of the form `{ program, function, bytes, finalizeCost }` and
+    /// `feeBytes` is null if the fee is unknown
+    pub fn to_object(&self) -> Result<Object, String> {
+        let transitions = Array::new_with_length(self.transitions.len() as u32);
+        for (index, transition) in self.transitions.iter().enumerate() {
+            let object = Object::new();
+            Reflect::set(&object, &"program".into(), &transition.program.clone().into())
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&object, &"function".into(), &transition.function.clone().into())
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&object, &"bytes".into(), &JsValue::from(transition.bytes as f64))
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&object, &"finalizeCost".into(), &JsValue::from(transition.finalize_cost as f64))
+                .map_err(|_| "Failed to set property")?;
+            transitions.set(index as u32, object.into());
+        }
+        let object = Object::new();
+        Reflect::set(&object, &"executionBytes".into(), &JsValue::from(self.execution_bytes as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"proofBytes".into(), &JsValue::from(self.proof_bytes as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"storageCost".into(), &JsValue::from(self.storage_cost as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"finalizeCost".into(), &JsValue::from(self.finalize_cost as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"totalCost".into(), &JsValue::from(self.total_cost as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(
+            &object,
+            &"feeBytes".into(),
+            &self.fee_bytes.map_or(JsValue::NULL, |bytes| JsValue::from(bytes as f64)),
+        )
+        .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"transitions".into(), &transitions.into()).map_err(|_| "Failed to set property")?;
+        Ok(object)
+    }
+}
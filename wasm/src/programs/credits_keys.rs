@@ -0,0 +1,218 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{key_checksum, KeyPair, Metadata, ProvingKey, VerifyingKey};
+
+use crate::types::native::{ProvingKeyNative, VerifyingKeyNative};
+use js_sys::Promise;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use wasm_bindgen_futures::future_to_promise;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+/// A function whose keys are published on the parameter server, along with the check identifying
+/// its proving key and its bundled verifying key
+pub struct PublishedFunction {
+    pub metadata: Metadata,
+    pub is_prover: fn(&ProvingKey) -> bool,
+    pub verifier: fn() -> VerifyingKey,
+}
+
+/// Look up a function whose keys are published by its name, such as `transfer_public`, or by its
+/// locator, such as `credits.aleo/transfer_public`
+pub fn published_function(function: &str) -> Result<PublishedFunction, String> {
+    type Published = (Metadata, fn(&ProvingKey) -> bool, fn() -> VerifyingKey);
+    let name = function.strip_prefix("credits.aleo/").unwrap_or(function);
+    let (metadata, is_prover, verifier): Published = match name {
+        "bond_public" => {
+            (Metadata::bond_public(), ProvingKey::is_bond_public_prover, VerifyingKey::bond_public_verifier)
+        }
+        "bond_validator" => {
+            (Metadata::bond_validator(), ProvingKey::is_bond_validator_prover, VerifyingKey::bond_validator_verifier)
+        }
+        "claim_unbond_public" => (
+            Metadata::claim_unbond_public(),
+            ProvingKey::is_claim_unbond_public_prover,
+            VerifyingKey::claim_unbond_public_verifier,
+        ),
+        "fee_private" => {
+            (Metadata::fee_private(), ProvingKey::is_fee_private_prover, VerifyingKey::fee_private_verifier)
+        }
+        "fee_public" => (Metadata::fee_public(), ProvingKey::is_fee_public_prover, VerifyingKey::fee_public_verifier),
+        "inclusion" => (Metadata::inclusion(), ProvingKey::is_inclusion_prover, VerifyingKey::inclusion_verifier),
+        "join" => (Metadata::join(), ProvingKey::is_join_prover, VerifyingKey::join_verifier),
+        "set_validator_state" => (
+            Metadata::set_validator_state(),
+            ProvingKey::is_set_validator_state_prover,
+            VerifyingKey::set_validator_state_verifier,
+        ),
+        "split" => (Metadata::split(), ProvingKey::is_split_prover, VerifyingKey::split_verifier),
+        "transfer_private" => (
+            Metadata::transfer_private(),
+            ProvingKey::is_transfer_private_prover,
+            VerifyingKey::transfer_private_verifier,
+        ),
+        "transfer_private_to_public" => (
+            Metadata::transfer_private_to_public(),
+            ProvingKey::is_transfer_private_to_public_prover,
+            VerifyingKey::transfer_private_to_public_verifier,
+        ),
+        "transfer_public" => {
+            (Metadata::transfer_public(), ProvingKey::is_transfer_public_prover, VerifyingKey::transfer_public_verifier)
+        }
+        "transfer_public_as_signer" => (
+            Metadata::transfer_public_as_signer(),
+            ProvingKey::is_transfer_public_as_signer_prover,
+            VerifyingKey::transfer_public_as_signer_verifier,
+        ),
+        "transfer_public_to_private" => (
+            Metadata::transfer_public_to_private(),
+            ProvingKey::is_transfer_public_to_private_prover,
+            VerifyingKey::transfer_public_to_private_verifier,
+        ),
+        "unbond_public" => {
+            (Metadata::unbond_public(), ProvingKey::is_unbond_public_prover, VerifyingKey::unbond_public_verifier)
+        }
+        _ => return Err(format!("No keys are published for the function {function}")),
+    };
+    Ok(PublishedFunction { metadata, is_prover, verifier })
+}
+
+// The location prover files are loaded from.
+#[derive(Clone, Debug)]
+enum ProverSource {
+    Url(String),
+    #[cfg(not(target_arch = "wasm32"))]
+    Directory(PathBuf),
+}
+
+/// Key provider loading the published keys of credits.aleo functions. Prover files are loaded
+/// from a parameter server or, in native builds, from a local directory mirroring it, checked against the checksum in
+/// the function's metadata and paired with the bundled verifying key of the function
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct CreditsKeyProvider {
+    source: ProverSource,
+}
+
+#[wasm_bindgen]
+impl CreditsKeyProvider {
+    /// Create a key provider downloading prover files from a parameter server
+    ///
+    /// @param {string | undefined} base_url (optional) The url prover files are downloaded from,
+    /// defaults to Metadata.baseUrl()
+    /// @returns {CreditsKeyProvider} A key provider downloading prover files from the url
+    #[wasm_bindgen(constructor)]
+    pub fn new(base_url: Option<String>) -> CreditsKeyProvider {
+        Self { source: ProverSource::Url(base_url.unwrap_or_else(Metadata::base_url)) }
+    }
+
+    /// Load the proving and verifying key of a credits.aleo function
+    ///
+    /// @param {string} function The name of the function, such as "transfer_public", or its
+    /// locator, such as "credits.aleo/transfer_public"
+    /// @returns {Promise<KeyPair>} The keys of the function. The promise is rejected if the loaded
+    /// proving key does not match the checksum published for the function
+    #[wasm_bindgen(js_name = functionKeys)]
+    pub fn function_keys_js(&self, function: &str) -> Promise {
+        let (provider, function) = (self.clone(), function.to_string());
+        future_to_promise(async move { Ok(JsValue::from(KeyPair::from(provider.function_keys(&function).await?))) })
+    }
+}
+
+impl CreditsKeyProvider {
+    /// Create a key provider reading prover files from a local directory which mirrors the
+    /// parameter server
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_directory(directory: impl Into<PathBuf>) -> Self {
+        Self { source: ProverSource::Directory(directory.into()) }
+    }
+
+    /// Load the proving and verifying key of a credits.aleo function
+    pub async fn function_keys(&self, function: &str) -> Result<(ProvingKeyNative, VerifyingKeyNative), String> {
+        let published = published_function(function)?;
+        let metadata = &published.metadata;
+        let bytes = self.prover_bytes(metadata).await?;
+
+        // Check the raw bytes first so corrupted downloads are rejected before being deserialized
+        let checksum = key_checksum(&bytes);
+        if checksum != metadata.prover_checksum {
+            return Err(format!(
+                "The prover file {} has checksum {checksum}, expected {}",
+                metadata.prover_file_name(),
+                metadata.prover_checksum
+            ));
+        }
+        let proving_key = ProvingKey::from_bytes(&bytes)?;
+        if !(published.is_prover)(&proving_key) {
+            return Err(format!("The proving key loaded for {} is not the {} prover", metadata.locator, metadata.name));
+        }
+        Ok((ProvingKeyNative::from(proving_key), VerifyingKeyNative::from((published.verifier)())))
+    }
+
+    /// Load the raw bytes of the prover file described by the metadata of a function
+    pub async fn prover_bytes(&self, metadata: &Metadata) -> Result<Vec<u8>, String> {
+        let file_name = metadata.prover_file_name();
+        match &self.source {
+            ProverSource::Url(base_url) => {
+                let url = format!("{}/{file_name}", base_url.trim_end_matches('/'));
+                let response = reqwest::get(&url).await.map_err(|e| format!("Failed to download {url}: {e}"))?;
+                if !response.status().is_success() {
+                    return Err(format!("Failed to download {url}: status {}", response.status()));
+                }
+                Ok(response.bytes().await.map_err(|e| format!("Failed to download {url}: {e}"))?.to_vec())
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            ProverSource::Directory(directory) => {
+                let path = directory.join(file_name);
+                std::fs::read(&path).map_err(|e| format!("Failed to read {}: {e}", path.display()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn test_published_function() {
+        let published = published_function("credits.aleo/fee_public").unwrap();
+        assert_eq!(published.metadata.name, "fee_public");
+        assert!((published.verifier)().is_fee_public_verifier());
+        assert_eq!(published_function("fee_public").unwrap().metadata.locator, "credits.aleo/fee_public");
+        assert!(published.metadata.prover.ends_with(published.metadata.prover_file_name()));
+        assert!(published_function("hello.aleo/main").is_err());
+        assert!(Metadata::from_function("credits.aleo/mint").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_credits_key_provider() {
+        let (proving_key, verifying_key) = CreditsKeyProvider::new(None).function_keys("fee_public").await.unwrap();
+        assert!(ProvingKey::from(proving_key).is_fee_public_prover());
+        assert!(VerifyingKey::from(verifying_key).is_fee_public_verifier());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_credits_key_provider_directory() {
+        let provider = CreditsKeyProvider::from_directory("missing_parameters");
+        let error = futures::executor::block_on(provider.function_keys("fee_public")).unwrap_err();
+        assert!(error.contains("Failed to read"));
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    types::native::{
+        Argument,
+        CurrentNetwork,
+        FutureNative,
+        LiteralNative,
+        LiteralType,
+        PlaintextNative,
+        ValueNative,
+        ValueType,
+    },
+    RecordPlaintext,
+};
+
+use js_sys::{Array, BigInt, Object, Reflect};
+use wasm_bindgen::JsValue;
+
+/// Decode an output of a function into a javascript object describing its declared type, its
+/// visibility and its value.
+///
+/// The visibility is one of `constant`, `public`, `private`, `record`, `external_record` or
+/// `future`. Values are decoded with `value_to_js`.
+pub fn output_to_js(value_type: &ValueType<CurrentNetwork>, value: &ValueNative) -> Result<Object, String> {
+    let (r#type, visibility) = match value_type {
+        ValueType::Constant(plaintext_type) => (plaintext_type.to_string(), "constant"),
+        ValueType::Public(plaintext_type) => (plaintext_type.to_string(), "public"),
+        ValueType::Private(plaintext_type) => (plaintext_type.to_string(), "private"),
+        ValueType::Record(record_name) => (record_name.to_string(), "record"),
+        ValueType::ExternalRecord(locator) => (locator.to_string(), "external_record"),
+        ValueType::Future(locator) => (locator.to_string(), "future"),
+    };
+    let output = Object::new();
+    Reflect::set(&output, &"type".into(), &r#type.into()).map_err(|_| "Failed to set property")?;
+    Reflect::set(&output, &"visibility".into(), &visibility.into()).map_err(|_| "Failed to set property")?;
+    Reflect::set(&output, &"value".into(), &value_to_js(value)?).map_err(|_| "Failed to set property")?;
+    Ok(output)
+}
+
+/// Decode an Aleo value into a javascript value. Plaintexts are decoded with `plaintext_to_js`,
+/// records become `RecordPlaintext` objects and futures are decoded with `future_to_js`.
+pub fn value_to_js(value: &ValueNative) -> Result<JsValue, String> {
+    match value {
+        ValueNative::Plaintext(plaintext) => plaintext_to_js(plaintext),
+        ValueNative::Record(record) => Ok(JsValue::from(RecordPlaintext::from(record.clone()))),
+        ValueNative::Future(future) => Ok(future_to_js(future)?.into()),
+    }
+}
+
+/// Decode a future into an object of the form
+/// `{ programId: string, functionName: string, arguments: Array }`. Arguments are decoded with
+/// `plaintext_to_js`, nested futures are decoded recursively.
+pub fn future_to_js(future: &FutureNative) -> Result<Object, String> {
+    let arguments = Array::new_with_length(future.arguments().len() as u32);
+    for (index, argument) in future.arguments().iter().enumerate() {
+        let argument = match argument {
+            Argument::Plaintext(plaintext) => plaintext_to_js(plaintext)?,
+            Argument::Future(future) => future_to_js(future)?.into(),
+        };
+        arguments.set(index as u32, argument);
+    }
+    let object = Object::new();
+    Reflect::set(&object, &"programId".into(), &future.program_id().to_string().into())
+        .map_err(|_| "Failed to set property")?;
+    Reflect::set(&object, &"functionName".into(), &future.function_name().to_string().into())
+        .map_err(|_| "Failed to set property")?;
+    Reflect::set(&object, &"arguments".into(), &arguments).map_err(|_| "Failed to set property")?;
+    Ok(object)
+}
+
+/// Decode a plaintext into a javascript value. Structs become objects, arrays become arrays and
+/// literals are decoded with `literal_to_js`.
+pub fn plaintext_to_js(plaintext: &PlaintextNative) -> Result<JsValue, String> {
+    match plaintext {
+        PlaintextNative::Literal(literal, _) => literal_to_js(literal),
+        PlaintextNative::Struct(members, _) => {
+            let object = Object::new();
+            for (name, member) in members.iter() {
+                Reflect::set(&object, &name.to_string().into(), &plaintext_to_js(member)?)
+                    .map_err(|_| "Failed to set property")?;
+            }
+            Ok(object.into())
+        }
+        PlaintextNative::Array(elements, _) => {
+            let array = Array::new_with_length(elements.len() as u32);
+            for (index, element) in elements.iter().enumerate() {
+                array.set(index as u32, plaintext_to_js(element)?);
+            }
+            Ok(array.into())
+        }
+    }
+}
+
+/// Decode a literal into a javascript value. Booleans become booleans, integers of up to 32 bits
+/// become numbers and larger integers become bigints. Every other literal is returned as its Aleo
+/// string representation (e.g. `1field` or an address).
+pub fn literal_to_js(literal: &LiteralNative) -> Result<JsValue, String> {
+    let literal_type = literal.to_type();
+    let string = literal.to_string();
+    let digits = string.strip_suffix(&literal_type.to_string()).unwrap_or(&string);
+    let invalid = |_| format!("Failed to decode the literal {string}");
+    Ok(match literal_type {
+        LiteralType::Boolean => JsValue::from_bool(string == "true"),
+        LiteralType::U8 | LiteralType::U16 | LiteralType::U32 => JsValue::from(digits.parse::<u32>().map_err(invalid)?),
+        LiteralType::I8 | LiteralType::I16 | LiteralType::I32 => JsValue::from(digits.parse::<i32>().map_err(invalid)?),
+        LiteralType::U64 | LiteralType::U128 => BigInt::from(digits.parse::<u128>().map_err(invalid)?).into(),
+        LiteralType::I64 | LiteralType::I128 => BigInt::from(digits.parse::<i128>().map_err(invalid)?).into(),
+        _ => JsValue::from_str(&string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn test_literal_to_js() {
+        let decode = |literal: &str| literal_to_js(&LiteralNative::from_str(literal).unwrap()).unwrap();
+        assert_eq!(decode("true").as_bool(), Some(true));
+        assert_eq!(decode("5u32").as_f64(), Some(5.0));
+        assert_eq!(decode("-5i8").as_f64(), Some(-5.0));
+        assert_eq!(BigInt::from(decode("18446744073709551615u64")), BigInt::from(u64::MAX));
+        assert_eq!(BigInt::from(decode("-2i128")), BigInt::from(-2i128));
+        assert_eq!(decode("1field").as_string(), Some("1field".to_string()));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_output_to_js() {
+        let value_type = ValueType::from_str("point.public").unwrap();
+        let value = ValueNative::from_str("{ x: 1u8, y: [2u64, 3u64] }").unwrap();
+        let output = output_to_js(&value_type, &value).unwrap();
+        assert_eq!(Reflect::get(&output, &"type".into()).unwrap().as_string(), Some("point".to_string()));
+        assert_eq!(Reflect::get(&output, &"visibility".into()).unwrap().as_string(), Some("public".to_string()));
+        let value = Reflect::get(&output, &"value".into()).unwrap();
+        assert_eq!(Reflect::get(&value, &"x".into()).unwrap().as_f64(), Some(1.0));
+        let y = Array::from(&Reflect::get(&value, &"y".into()).unwrap());
+        assert_eq!(BigInt::from(y.get(1)), BigInt::from(3u64));
+
+        let value_type = ValueType::from_str("credits.aleo/transfer_public.future").unwrap();
+        let value = ValueNative::from_str(
+            "{ program_id: credits.aleo, function_name: transfer_public, arguments: [ aleo1cfr9mr3zum3h8tadfzan6dvvjh3cj3s0v8ha22zp88cpfn0n3gxq4m0dd0, 5u64 ] }",
+        )
+        .unwrap();
+        let output = output_to_js(&value_type, &value).unwrap();
+        assert_eq!(Reflect::get(&output, &"visibility".into()).unwrap().as_string(), Some("future".to_string()));
+        let future = Reflect::get(&output, &"value".into()).unwrap();
+        assert_eq!(Reflect::get(&future, &"programId".into()).unwrap().as_string(), Some("credits.aleo".to_string()));
+        assert_eq!(
+            Reflect::get(&future, &"functionName".into()).unwrap().as_string(),
+            Some("transfer_public".to_string())
+        );
+        let arguments = Array::from(&Reflect::get(&future, &"arguments".into()).unwrap());
+        assert_eq!(arguments.length(), 2);
+        assert_eq!(BigInt::from(arguments.get(1)), BigInt::from(5u64));
+    }
+}
@@ -0,0 +1,516 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    program_checksum,
+    types::native::{
+        BlockNative,
+        BlockStoreNative,
+        ConfirmedTransaction,
+        CurrentAleo,
+        CurrentNetwork,
+        FieldNative,
+        FinalizeGlobalState,
+        Header,
+        Metadata,
+        Network,
+        PrivateKeyNative,
+        ProcessNative,
+        ProgramIDNative,
+        ProgramNative,
+        Ratifications,
+        Rejected,
+        Solutions,
+        ToBits,
+        TransactionNative,
+        Transactions,
+        FINALIZE_OPERATIONS_DEPTH,
+    },
+    DeployedProgramQuery,
+    FinalizeOutcome,
+    MappingStore,
+    OfflineQuery,
+    ProgramResolver,
+    Transaction,
+};
+use snarkvm_console::program::StatePath;
+use snarkvm_ledger_query::QueryTrait;
+
+use async_trait::async_trait;
+use indexmap::IndexSet;
+use js_sys::{Object, Reflect};
+use rand::{rngs::StdRng, SeedableRng};
+use std::str::FromStr;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+/// An in-process Aleo ledger for testing programs and transactions offline.
+///
+/// Transactions built by the `ProgramManager` can be submitted to the devnet, which verifies them,
+/// runs their finalize logic against its mappings and includes them in a new block. The devnet
+/// maintains the commitment tree of the records it has seen, so the records created by previous
+/// transactions can be spent by later ones using the offline query returned by `toOfflineQuery`.
+#[wasm_bindgen]
+pub struct Devnet {
+    process: ProcessNative,
+    blocks: BlockStoreNative,
+    mappings: MappingStore,
+    beacon: PrivateKeyNative,
+    commitments: IndexSet<FieldNative>,
+}
+
+#[wasm_bindgen]
+impl Devnet {
+    /// Create an empty devnet containing only the credits.aleo program
+    ///
+    /// @returns {Devnet | Error} An empty devnet
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Result<Devnet, String> {
+        let mut mappings = MappingStore::new()?;
+        let process = ProcessNative::load_web().map_err(|e| e.to_string())?;
+        let credits = ProgramNative::credits().map_err(|e| e.to_string())?;
+        for mapping_name in credits.mappings().keys() {
+            mappings.initialize_mapping(*credits.id(), *mapping_name)?;
+        }
+        Ok(Self {
+            process,
+            blocks: BlockStoreNative::open(None::<u16>).map_err(|e| e.to_string())?,
+            mappings,
+            beacon: PrivateKeyNative::new(&mut StdRng::from_entropy()).map_err(|e| e.to_string())?,
+            commitments: IndexSet::new(),
+        })
+    }
+
+    /// Add a program to the devnet without deploying it, initializing its mappings. Imports of the
+    /// program must be added first
+    ///
+    /// @param {string} program The source code of the program
+    #[wasm_bindgen(js_name = "addProgram")]
+    pub fn add_program(&mut self, program: &str) -> Result<(), String> {
+        let program = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
+        if self.process.contains_program(program.id()) {
+            return Err(format!("{} already exists in the devnet", program.id()));
+        }
+        self.process.add_program(&program).map_err(|e| e.to_string())?;
+        for mapping_name in program.mappings().keys() {
+            self.mappings.initialize_mapping(*program.id(), *mapping_name)?;
+        }
+        Ok(())
+    }
+
+    /// Set the value of a key in a mapping, for example to fund an account in the credits.aleo
+    /// `account` mapping
+    ///
+    /// @param {string} program_id The id of the program the mapping belongs to
+    /// @param {string} mapping_name The name of the mapping
+    /// @param {string} key The key as an Aleo plaintext string
+    /// @param {string} value The value as an Aleo plaintext string
+    #[wasm_bindgen(js_name = "setMappingValue")]
+    pub fn set_mapping_value(
+        &mut self,
+        program_id: &str,
+        mapping_name: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<(), String> {
+        self.mappings.set_value(program_id, mapping_name, key, value)
+    }
+
+    /// Get the value of a key in a mapping
+    ///
+    /// @param {string} program_id The id of the program the mapping belongs to
+    /// @param {string} mapping_name The name of the mapping
+    /// @param {string} key The key as an Aleo plaintext string
+    /// @returns {string | undefined} The value of the key if it is present in the mapping
+    #[wasm_bindgen(js_name = "getMappingValue")]
+    pub fn get_mapping_value(&self, program_id: &str, mapping_name: &str, key: &str) -> Result<Option<String>, String> {
+        self.mappings.get_value(program_id, mapping_name, key)
+    }
+
+    /// Get the source code of a program in the devnet
+    ///
+    /// @param {string} program_id The id of the program
+    /// @returns {string | Error} The source code of the program
+    #[wasm_bindgen(js_name = "getProgram")]
+    pub fn get_program(&self, program_id: &str) -> Result<String, String> {
+        let program_id = ProgramIDNative::from_str(program_id).map_err(|e| e.to_string())?;
+        Ok(self.process.get_program(program_id).map_err(|e| e.to_string())?.to_string())
+    }
+
+    /// Get the height of the latest block, undefined if no transaction has been submitted yet
+    ///
+    /// @returns {number | undefined} The height of the latest block
+    #[wasm_bindgen(js_name = "latestHeight")]
+    pub fn latest_height(&self) -> Option<u32> {
+        self.blocks.max_height()
+    }
+
+    /// Get the current state root of the devnet
+    ///
+    /// @returns {string} The current state root
+    #[wasm_bindgen(js_name = "stateRoot")]
+    pub fn state_root(&self) -> String {
+        self.blocks.current_state_root().to_string()
+    }
+
+    /// Get the state path of a record commitment included in the devnet
+    ///
+    /// @param {string} commitment The commitment of the record
+    /// @returns {string | Error} The state path of the commitment
+    #[wasm_bindgen(js_name = "getStatePath")]
+    pub fn get_state_path(&self, commitment: &str) -> Result<String, String> {
+        let commitment = FieldNative::from_str(commitment).map_err(|e| e.to_string())?;
+        Ok(self.blocks.get_state_path_for_commitment(&commitment).map_err(|e| e.to_string())?.to_string())
+    }
+
+    /// Create an offline query containing the current state root and the state paths of every
+    /// record created in the devnet. It can be passed to the `ProgramManager` to build
+    /// transactions spending records of the devnet
+    ///
+    /// @returns {OfflineQuery | Error} An offline query for the current state of the devnet
+    #[wasm_bindgen(js_name = "toOfflineQuery")]
+    pub fn to_offline_query(&self) -> Result<OfflineQuery, String> {
+        let mut offline_query = OfflineQuery::new(&self.state_root())?;
+        for commitment in self.commitments.iter() {
+            let state_path = self.blocks.get_state_path_for_commitment(commitment).map_err(|e| e.to_string())?;
+            offline_query.add_state_path(&commitment.to_string(), &state_path.to_string())?;
+        }
+        Ok(offline_query)
+    }
+
+    /// Verify a transaction, run its finalize logic and include it in a new block. Executions
+    /// whose finalize logic fails are included as rejected and only their fee is applied
+    ///
+    /// @param {Transaction} transaction The transaction to submit
+    /// @returns {Object | Error} An object of the form \{ height: number, transactionId: string,
+    /// accepted: boolean, error: string | null, diffs: Array \} describing the block the
+    /// transaction was included in and the changes it made to the mappings
+    #[wasm_bindgen(js_name = "submitTransaction")]
+    pub fn submit_transaction(&mut self, transaction: &Transaction) -> Result<Object, String> {
+        let transaction = TransactionNative::from(transaction.clone());
+        self.verify_transaction(&transaction)?;
+
+        let height = self.blocks.max_height().map_or(0, |height| height + 1);
+        let (confirmed, outcome) = self.finalize_transaction(transaction.clone(), height)?;
+
+        // Track the records included in the block so they can be spent later. Rejected executions
+        // are confirmed as their fee transaction, so only the records of the fee are included.
+        let commitments = confirmed
+            .transaction()
+            .transitions()
+            .flat_map(|transition| transition.commitments().cloned())
+            .collect::<Vec<_>>();
+        self.add_block(height, confirmed)?;
+        self.commitments.extend(commitments);
+
+        let result = outcome.to_object()?;
+        Reflect::set(&result, &"height".into(), &JsValue::from(height)).map_err(|_| "Failed to set property")?;
+        Reflect::set(&result, &"transactionId".into(), &transaction.id().to_string().into())
+            .map_err(|_| "Failed to set property")?;
+        Ok(result)
+    }
+}
+
+impl Devnet {
+    // Verify the proofs of a transaction, that its state roots are known to the devnet and that
+    // it does not spend records that were already spent.
+    fn verify_transaction(&mut self, transaction: &TransactionNative) -> Result<(), String> {
+        let rng = &mut StdRng::from_entropy();
+        match transaction {
+            TransactionNative::Deploy(_, _, deployment, fee) => {
+                if self.process.contains_program(deployment.program_id()) {
+                    return Err(format!("{} is already deployed", deployment.program_id()));
+                }
+                self.process.verify_deployment::<CurrentAleo, _>(deployment, rng).map_err(|e| e.to_string())?;
+                let deployment_id = deployment.to_deployment_id().map_err(|e| e.to_string())?;
+                self.process.verify_fee(fee, deployment_id).map_err(|e| e.to_string())?;
+            }
+            TransactionNative::Execute(_, execution, fee) => {
+                self.process.verify_execution(execution).map_err(|e| e.to_string())?;
+                self.check_state_root(&execution.global_state_root())?;
+                if let Some(fee) = fee {
+                    let execution_id = execution.to_execution_id().map_err(|e| e.to_string())?;
+                    self.process.verify_fee(fee, execution_id).map_err(|e| e.to_string())?;
+                }
+            }
+            TransactionNative::Fee(..) => return Err("Fee transactions cannot be submitted to the devnet".to_string()),
+        }
+        if let Some(fee) = transaction.fee_transition() {
+            self.check_state_root(&fee.global_state_root())?;
+        }
+
+        for serial_number in transaction.serial_numbers() {
+            if self.blocks.transition_store().contains_serial_number(serial_number).map_err(|e| e.to_string())? {
+                return Err(format!("The record with serial number {serial_number} was already spent"));
+            }
+        }
+        Ok(())
+    }
+
+    // Ensure a state root is the current state root or the state root of a previous block.
+    fn check_state_root(&self, state_root: &<CurrentNetwork as Network>::StateRoot) -> Result<(), String> {
+        if *state_root != self.blocks.current_state_root()
+            && !self.blocks.contains_state_root(state_root).map_err(|e| e.to_string())?
+        {
+            return Err(format!("The state root {state_root} is unknown to the devnet"));
+        }
+        Ok(())
+    }
+
+    // Run the finalize logic of a transaction and confirm it.
+    fn finalize_transaction(
+        &mut self,
+        transaction: TransactionNative,
+        height: u32,
+    ) -> Result<(ConfirmedTransaction<CurrentNetwork>, FinalizeOutcome), String> {
+        let state = FinalizeGlobalState::new::<CurrentNetwork>(height as u64, height, 0, 0, Default::default())
+            .map_err(|e| e.to_string())?;
+
+        // Collect the programs whose mappings may change.
+        let mut program_ids = IndexSet::new();
+        for transition in transaction.transitions() {
+            if self.process.contains_program(transition.program_id()) {
+                MappingStore::collect_programs(&self.process, transition.program_id(), &mut program_ids)?;
+            }
+        }
+        if let TransactionNative::Deploy(_, _, deployment, _) = &transaction {
+            for import in deployment.program().imports().keys() {
+                MappingStore::collect_programs(&self.process, import, &mut program_ids)?;
+            }
+        }
+        let mut programs = program_ids
+            .iter()
+            .map(|program_id| self.process.get_program(program_id).cloned().map_err(|e| e.to_string()))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        match &transaction {
+            TransactionNative::Deploy(_, _, deployment, fee) => {
+                programs.push(deployment.program().clone());
+                let process = &self.process;
+                let (outcome, result) = self
+                    .mappings
+                    .finalize_with(&programs, |store| process.finalize_deployment(state, store, deployment, fee))?;
+                let (stack, operations) = result.ok_or_else(|| {
+                    format!("The deployment was rejected: {}", outcome.error.clone().unwrap_or_default())
+                })?;
+                self.process.add_stack(stack);
+                let confirmed =
+                    ConfirmedTransaction::accepted_deploy(0, transaction, operations).map_err(|e| e.to_string())?;
+                Ok((confirmed, outcome))
+            }
+            TransactionNative::Execute(_, execution, fee) => {
+                let process = &self.process;
+                let (outcome, result) = self.mappings.finalize_with(&programs, |store| {
+                    process.finalize_execution(state, store, execution, fee.as_ref())
+                })?;
+                if let Some(operations) = result {
+                    let confirmed = ConfirmedTransaction::accepted_execute(0, transaction, operations)
+                        .map_err(|e| e.to_string())?;
+                    return Ok((confirmed, outcome));
+                }
+
+                // If the execution is rejected, only its fee is applied.
+                let fee = fee.clone().ok_or_else(|| {
+                    format!("The execution was rejected: {}", outcome.error.clone().unwrap_or_default())
+                })?;
+                let (_, operations) =
+                    self.mappings.finalize_with(&programs, |store| process.finalize_fee(state, store, &fee))?;
+                let operations =
+                    operations.ok_or("The fee of the rejected execution could not be applied".to_string())?;
+                let fee_transaction = TransactionNative::from_fee(fee).map_err(|e| e.to_string())?;
+                let confirmed = ConfirmedTransaction::rejected_execute(
+                    0,
+                    fee_transaction,
+                    Rejected::new_execution(execution.clone()),
+                    operations,
+                )
+                .map_err(|e| e.to_string())?;
+                Ok((confirmed, outcome))
+            }
+            TransactionNative::Fee(..) => Err("Fee transactions cannot be submitted to the devnet".to_string()),
+        }
+    }
+
+    // Create a block containing a confirmed transaction and add it to the block store.
+    fn add_block(&mut self, height: u32, confirmed: ConfirmedTransaction<CurrentNetwork>) -> Result<(), String> {
+        let transactions = Transactions::from_iter([confirmed]);
+        let ratifications = Ratifications::try_from(vec![]).map_err(|e| e.to_string())?;
+        let solutions = Solutions::from(None);
+
+        let (previous_hash, header) = if height == 0 {
+            let header = Header::genesis(&ratifications, &transactions, vec![]).map_err(|e| e.to_string())?;
+            (Default::default(), header)
+        } else {
+            let previous_hash = self
+                .blocks
+                .get_block_hash(height - 1)
+                .map_err(|e| e.to_string())?
+                .ok_or("The previous block of the devnet is missing".to_string())?;
+            let metadata = Metadata::new(
+                CurrentNetwork::ID,
+                height as u64,
+                height,
+                0,
+                0,
+                CurrentNetwork::GENESIS_COINBASE_TARGET,
+                CurrentNetwork::GENESIS_PROOF_TARGET,
+                CurrentNetwork::GENESIS_COINBASE_TARGET,
+                CurrentNetwork::GENESIS_TIMESTAMP,
+                CurrentNetwork::GENESIS_TIMESTAMP + height as i64,
+            )
+            .map_err(|e| e.to_string())?;
+            let header = Header::from(
+                self.blocks.current_state_root(),
+                transactions.to_transactions_root().map_err(|e| e.to_string())?,
+                Self::finalize_root(&transactions)?,
+                ratifications.to_ratifications_root().map_err(|e| e.to_string())?,
+                solutions.to_solutions_root().map_err(|e| e.to_string())?,
+                FieldNative::zero(),
+                metadata,
+            )
+            .map_err(|e| e.to_string())?;
+            (previous_hash, header)
+        };
+
+        let block = BlockNative::new_beacon(
+            &self.beacon,
+            previous_hash,
+            header,
+            ratifications,
+            solutions,
+            vec![],
+            transactions,
+            vec![],
+            &mut StdRng::from_entropy(),
+        )
+        .map_err(|e| e.to_string())?;
+        self.blocks.insert(&block).map_err(|e| e.to_string())
+    }
+
+    // Compute the finalize root of a block from the finalize operations of its confirmed
+    // transactions. The devnet creates no ratifications, so no ratified operations are appended.
+    fn finalize_root(transactions: &Transactions<CurrentNetwork>) -> Result<FieldNative, String> {
+        let leaves = transactions.finalize_operations().map(ToBits::to_bits_le).collect::<Vec<_>>();
+        let tree = CurrentNetwork::merkle_tree_bhp::<FINALIZE_OPERATIONS_DEPTH>(&leaves).map_err(|e| e.to_string())?;
+        Ok(*tree.root())
+    }
+}
+
+#[async_trait(?Send)]
+impl QueryTrait<CurrentNetwork> for Devnet {
+    fn current_state_root(&self) -> anyhow::Result<<CurrentNetwork as Network>::StateRoot> {
+        Ok(self.blocks.current_state_root())
+    }
+
+    async fn current_state_root_async(&self) -> anyhow::Result<<CurrentNetwork as Network>::StateRoot> {
+        Ok(self.blocks.current_state_root())
+    }
+
+    fn get_state_path_for_commitment(&self, commitment: &FieldNative) -> anyhow::Result<StatePath<CurrentNetwork>> {
+        self.blocks.get_state_path_for_commitment(commitment)
+    }
+
+    async fn get_state_path_for_commitment_async(
+        &self,
+        commitment: &FieldNative,
+    ) -> anyhow::Result<StatePath<CurrentNetwork>> {
+        self.blocks.get_state_path_for_commitment(commitment)
+    }
+}
+
+#[async_trait(?Send)]
+impl ProgramResolver for Devnet {
+    async fn program_source(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        self.process
+            .get_program(*program_id)
+            .map(|program| program.to_string())
+            .map_err(|_| format!("Program {program_id} is not deployed on the devnet"))
+    }
+}
+
+#[async_trait(?Send)]
+impl DeployedProgramQuery for Devnet {
+    async fn deployed_checksum(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        let program = self
+            .process
+            .get_program(*program_id)
+            .map_err(|_| format!("Program {program_id} is not deployed on the devnet"))?;
+        program_checksum(program)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ImportResolver, MemoryResolver};
+
+    use wasm_bindgen_test::*;
+
+    const ADDRESS: &str = "aleo1cfr9mr3zum3h8tadfzan6dvvjh3cj3s0v8ha22zp88cpfn0n3gxq4m0dd0";
+
+    const COUNTER_PROGRAM: &str = r#"program devnet_counter.aleo;
+
+mapping counter:
+    key as address.public;
+    value as u64.public;
+"#;
+
+    #[wasm_bindgen_test]
+    fn test_devnet_state() {
+        let mut devnet = Devnet::new().unwrap();
+        assert_eq!(devnet.latest_height(), None);
+
+        // Programs can be added without a deployment and their mappings are initialized
+        devnet.add_program(COUNTER_PROGRAM).unwrap();
+        assert!(devnet.add_program(COUNTER_PROGRAM).is_err());
+        assert_eq!(
+            devnet.get_program("devnet_counter.aleo").unwrap(),
+            ProgramNative::from_str(COUNTER_PROGRAM).unwrap().to_string()
+        );
+        assert_eq!(devnet.get_mapping_value("devnet_counter.aleo", "counter", ADDRESS).unwrap(), None);
+
+        // Accounts can be funded through the credits.aleo account mapping
+        devnet.set_mapping_value("credits.aleo", "account", ADDRESS, "5000000u64").unwrap();
+        assert_eq!(
+            devnet.get_mapping_value("credits.aleo", "account", ADDRESS).unwrap(),
+            Some("5000000u64".to_string())
+        );
+
+        // An offline query can be created before any block exists
+        let offline_query = devnet.to_offline_query().unwrap();
+        assert_eq!(offline_query.current_state_root().unwrap().to_string(), devnet.state_root());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_devnet_import_pinning() {
+        let mut devnet = Devnet::new().unwrap();
+        devnet.add_program(COUNTER_PROGRAM).unwrap();
+
+        // The on-chain checksum of a program matches the checksum of its source code
+        let program_id = ProgramIDNative::from_str("devnet_counter.aleo").unwrap();
+        let counter = ProgramNative::from_str(COUNTER_PROGRAM).unwrap();
+        assert_eq!(devnet.deployed_checksum(&program_id).await.unwrap(), program_checksum(&counter).unwrap());
+        assert!(devnet.deployed_checksum(&ProgramIDNative::from_str("missing.aleo").unwrap()).await.is_err());
+
+        // An outdated import is rejected when pinned to the deployed program
+        let outdated = COUNTER_PROGRAM.replace("u64", "u128");
+        let mut imports = MemoryResolver::new();
+        imports.insert("devnet_counter.aleo", &outdated);
+        let mut resolver = ImportResolver::new(imports);
+        resolver.pin_onchain(program_id, &devnet).await.unwrap();
+        let error = resolver.resolve(&program_id).await.unwrap_err();
+        assert!(error.contains("is pinned to checksum"));
+    }
+}
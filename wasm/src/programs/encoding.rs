@@ -0,0 +1,244 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::types::native::{CurrentNetwork, IdentifierNative, LiteralType, PlaintextType, ProgramNative, ValueType};
+
+use js_sys::{Array, BigInt, Object, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Convert the javascript inputs of a function call into the Aleo strings expected by the
+/// function, using the input types declared by the function.
+///
+/// Strings are passed through unchanged. Numbers and bigints are suffixed with their literal type
+/// (5 becomes 5u32 when the input is a u32), booleans become `true` or `false`, objects become
+/// structs with their members encoded in declaration order, arrays become Aleo arrays and objects
+/// passed as records (such as `RecordPlaintext` instances) are converted with their `toString`
+/// method.
+pub fn encode_inputs(
+    program: &ProgramNative,
+    function_id: &IdentifierNative,
+    inputs: &Array,
+) -> Result<Vec<String>, String> {
+    let function = program.get_function(function_id).map_err(|e| e.to_string())?;
+    let input_types = function.input_types();
+
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| {
+            if let Some(input) = input.as_string() {
+                return Ok(input);
+            }
+            let value_type = input_types.get(index).ok_or_else(|| {
+                format!("Invalid input - {function_id} takes {} inputs, found {}", input_types.len(), inputs.length())
+            })?;
+            encode_value(program, value_type, &input).map_err(|e| format!("Invalid input {index} - {e}"))
+        })
+        .collect()
+}
+
+// Encode a javascript value as an input of the given value type.
+fn encode_value(
+    program: &ProgramNative,
+    value_type: &ValueType<CurrentNetwork>,
+    value: &JsValue,
+) -> Result<String, String> {
+    match value_type {
+        ValueType::Constant(plaintext_type)
+        | ValueType::Public(plaintext_type)
+        | ValueType::Private(plaintext_type) => encode_plaintext(program, plaintext_type, value),
+        ValueType::Record(_) | ValueType::ExternalRecord(_) => {
+            if let Some(record) = value.as_string() {
+                return Ok(record);
+            }
+            let object =
+                value.dyn_ref::<Object>().ok_or("records must be a string or a RecordPlaintext".to_string())?;
+            let record = String::from(object.to_string());
+            if record == "[object Object]" {
+                return Err("records must be a string or a RecordPlaintext".to_string());
+            }
+            Ok(record)
+        }
+        ValueType::Future(_) => Err("futures cannot be provided as inputs".to_string()),
+    }
+}
+
+// Encode a javascript value as a plaintext of the given type.
+fn encode_plaintext(
+    program: &ProgramNative,
+    plaintext_type: &PlaintextType<CurrentNetwork>,
+    value: &JsValue,
+) -> Result<String, String> {
+    match plaintext_type {
+        PlaintextType::Literal(literal_type) => encode_literal(literal_type, value),
+        PlaintextType::Struct(struct_name) => {
+            if let Some(value) = value.as_string() {
+                return Ok(value);
+            }
+            if !value.is_object() || Array::is_array(value) {
+                return Err(format!("expected an object for the {struct_name} struct"));
+            }
+            let struct_type = program.get_struct(struct_name).map_err(|e| e.to_string())?;
+            let members = struct_type
+                .members()
+                .iter()
+                .map(|(member_name, member_type)| {
+                    let member = Reflect::get(value, &member_name.to_string().into())
+                        .map_err(|_| format!("failed to read the '{member_name}' member"))?;
+                    if member.is_undefined() {
+                        return Err(format!("the '{member_name}' member of the {struct_name} struct is missing"));
+                    }
+                    let member = encode_plaintext(program, member_type, &member)
+                        .map_err(|e| format!("member '{member_name}': {e}"))?;
+                    Ok(format!("{member_name}: {member}"))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(format!("{{ {} }}", members.join(", ")))
+        }
+        PlaintextType::Array(array_type) => {
+            if let Some(value) = value.as_string() {
+                return Ok(value);
+            }
+            let array = value.dyn_ref::<Array>().ok_or(format!("expected an array for the {plaintext_type} type"))?;
+            let elements = array
+                .iter()
+                .enumerate()
+                .map(|(index, element)| {
+                    encode_plaintext(program, array_type.next_element_type(), &element)
+                        .map_err(|e| format!("element {index}: {e}"))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(format!("[{}]", elements.join(", ")))
+        }
+    }
+}
+
+// Encode a javascript value as a literal of the given type.
+fn encode_literal(literal_type: &LiteralType, value: &JsValue) -> Result<String, String> {
+    if let Some(boolean) = value.as_bool() {
+        return match literal_type {
+            LiteralType::Boolean => Ok(boolean.to_string()),
+            _ => Err(format!("expected a {literal_type} literal, found a boolean")),
+        };
+    }
+
+    let number = if value.is_bigint() {
+        let bigint = value.unchecked_ref::<BigInt>();
+        String::from(bigint.to_string(10).map_err(|_| "failed to convert the bigint to a string".to_string())?)
+    } else if let Some(number) = value.as_f64() {
+        if number.fract() != 0.0 || number.abs() > 9_007_199_254_740_991.0 {
+            return Err(format!("{number} is not a safe integer, use a bigint or a string instead"));
+        }
+        format!("{number:.0}")
+    } else if let Some(string) = value.as_string() {
+        return match literal_type {
+            LiteralType::String if !string.starts_with('"') => Ok(format!("{string:?}")),
+            _ => Ok(string),
+        };
+    } else {
+        return Err(format!("expected a {literal_type} literal"));
+    };
+
+    match literal_type {
+        LiteralType::Field
+        | LiteralType::Group
+        | LiteralType::Scalar
+        | LiteralType::I8
+        | LiteralType::I16
+        | LiteralType::I32
+        | LiteralType::I64
+        | LiteralType::I128
+        | LiteralType::U8
+        | LiteralType::U16
+        | LiteralType::U32
+        | LiteralType::U64
+        | LiteralType::U128 => Ok(format!("{number}{literal_type}")),
+        _ => Err(format!("a number cannot be used as a {literal_type} literal")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use wasm_bindgen_test::*;
+
+    const PROGRAM: &str = r#"program encoding_test.aleo;
+
+struct point:
+    x as i32;
+    y as i32;
+
+record Token:
+    owner as address.private;
+    amount as u64.private;
+
+function encode:
+    input r0 as u32.public;
+    input r1 as u128.private;
+    input r2 as boolean.private;
+    input r3 as point.private;
+    input r4 as [u8; 2u32].private;
+    input r5 as Token.record;
+    output r0 as u32.public;
+"#;
+
+    const RECORD: &str = "{ owner: aleo1cfr9mr3zum3h8tadfzan6dvvjh3cj3s0v8ha22zp88cpfn0n3gxq4m0dd0.private, amount: 100u64.private, _nonce: 0group.public }";
+
+    #[wasm_bindgen_test]
+    fn test_encode_inputs() {
+        let program = ProgramNative::from_str(PROGRAM).unwrap();
+        let function = IdentifierNative::from_str("encode").unwrap();
+
+        let point = Object::new();
+        Reflect::set(&point, &"x".into(), &JsValue::from(-1)).unwrap();
+        Reflect::set(&point, &"y".into(), &JsValue::from(2)).unwrap();
+        let bytes = Array::of2(&JsValue::from(1), &JsValue::from(2));
+        let record = crate::RecordPlaintext::from_string(RECORD).unwrap();
+
+        let inputs = Array::new();
+        inputs.push(&JsValue::from(5));
+        inputs.push(&JsValue::from(BigInt::from(u128::MAX)));
+        inputs.push(&JsValue::from(true));
+        inputs.push(&point);
+        inputs.push(&bytes);
+        inputs.push(&JsValue::from(record));
+
+        let encoded = encode_inputs(&program, &function, &inputs).unwrap();
+        assert_eq!(encoded[0], "5u32");
+        assert_eq!(encoded[1], format!("{}u128", u128::MAX));
+        assert_eq!(encoded[2], "true");
+        assert_eq!(encoded[3], "{ x: -1i32, y: 2i32 }");
+        assert_eq!(encoded[4], "[1u8, 2u8]");
+        assert_eq!(
+            crate::types::native::RecordPlaintextNative::from_str(&encoded[5]).unwrap(),
+            crate::types::native::RecordPlaintextNative::from_str(RECORD).unwrap()
+        );
+
+        // Strings are passed through unchanged
+        let inputs = Array::of1(&JsValue::from_str("7u32"));
+        assert_eq!(encode_inputs(&program, &function, &inputs).unwrap(), vec!["7u32".to_string()]);
+
+        // Values that cannot be represented are rejected
+        assert!(encode_inputs(&program, &function, &Array::of1(&JsValue::from(1.5))).is_err());
+        assert!(encode_inputs(&program, &function, &Array::of1(&JsValue::from(f64::MAX))).is_err());
+        let incomplete = Object::new();
+        Reflect::set(&incomplete, &"x".into(), &JsValue::from(1)).unwrap();
+        let inputs = Array::of4(&JsValue::from(1), &JsValue::from(1), &JsValue::from(true), &incomplete);
+        assert!(encode_inputs(&program, &function, &inputs).unwrap_err().contains("'y'"));
+    }
+}
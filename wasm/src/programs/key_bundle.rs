@@ -0,0 +1,406 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{key_checksum, KeyPair, ProvingKey, VerifyingKey};
+
+use crate::types::native::{
+    CurrentNetwork,
+    FromBytes,
+    IdentifierNative,
+    Network,
+    ProgramIDNative,
+    ProvingKeyNative,
+    ToBytes,
+    VerifyingKeyNative,
+};
+use std::{
+    io::{Error, ErrorKind, Read, Result as IoResult, Write},
+    str::FromStr,
+};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// The version of snarkVM the SDK is built against, recorded in key bundles. It is the version
+/// and abbreviated git revision of the snarkVM dependencies pinned in Cargo.toml and must be
+/// updated together with them, which `test_snarkvm_version_matches_manifest` checks
+pub const SNARKVM_VERSION: &str = "0.16.19+be171ce";
+
+/// A distributable file holding the proving and verifying key of a function along with the
+/// program id, function name, network and snarkVM version they were produced for and the
+/// checksums of both keys.
+///
+/// The file starts with the `ALEOKEYS` magic bytes and a format version, followed by the network
+/// id, the snarkVM version, the program id, the function name, both checksums and finally the
+/// length prefixed bytes of the proving and verifying key. Reading a bundle rejects files for
+/// another network or snarkVM version and files whose keys do not match their checksums.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBundle {
+    program_id: ProgramIDNative,
+    function_id: IdentifierNative,
+    network: u16,
+    snarkvm_version: String,
+    proving_key_checksum: String,
+    verifying_key_checksum: String,
+    proving_key: Vec<u8>,
+    verifying_key: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl KeyBundle {
+    /// Create a key bundle for the keys of a function
+    ///
+    /// @param {string} program_id The id of the program
+    /// @param {string} function The name of the function
+    /// @param {ProvingKey} proving_key The proving key of the function
+    /// @param {VerifyingKey} verifying_key The verifying key of the function
+    /// @returns {KeyBundle | Error} A key bundle for the current network and snarkVM version
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        program_id: &str,
+        function: &str,
+        proving_key: &ProvingKey,
+        verifying_key: &VerifyingKey,
+    ) -> Result<KeyBundle, String> {
+        let program_id = ProgramIDNative::from_str(program_id).map_err(|e| e.to_string())?;
+        let function_id = IdentifierNative::from_str(function).map_err(|e| e.to_string())?;
+        Self::from_native(program_id, function_id, proving_key, verifying_key)
+    }
+
+    /// Create a key bundle from a key pair, such as the one returned by synthesizeKeyPair. The key
+    /// pair keeps its keys
+    ///
+    /// @param {string} program_id The id of the program
+    /// @param {string} function The name of the function
+    /// @param {KeyPair} key_pair The keys of the function
+    /// @returns {KeyBundle | Error} A key bundle for the current network and snarkVM version
+    #[wasm_bindgen(js_name = fromKeyPair)]
+    pub fn from_key_pair(program_id: &str, function: &str, key_pair: &KeyPair) -> Result<KeyBundle, String> {
+        let (proving_key, verifying_key) = key_pair.keys()?;
+        Self::new(program_id, function, proving_key, verifying_key)
+    }
+
+    /// Read a key bundle from bytes, checking that it targets the current network and snarkVM
+    /// version and that its keys match their checksums
+    ///
+    /// @param {Uint8Array} bytes The bytes of a key bundle file
+    /// @returns {KeyBundle | Error} The key bundle
+    #[wasm_bindgen]
+    pub fn read(bytes: &[u8]) -> Result<KeyBundle, String> {
+        let mut reader = bytes;
+        let bundle = Self::read_le(&mut reader).map_err(|e| format!("Failed to read key bundle: {e}"))?;
+        if !reader.is_empty() {
+            return Err("Failed to read key bundle: unexpected trailing bytes".to_string());
+        }
+        bundle.verify()?;
+        Ok(bundle)
+    }
+
+    /// Write the key bundle to bytes
+    ///
+    /// @returns {Uint8Array | Error} The bytes of the key bundle file
+    #[wasm_bindgen]
+    pub fn write(&self) -> Result<Vec<u8>, String> {
+        self.to_bytes_le().map_err(|_| "Failed to write key bundle".to_string())
+    }
+
+    /// @returns {string} The id of the program
+    #[wasm_bindgen(js_name = programId)]
+    pub fn program_id(&self) -> String {
+        self.program_id.to_string()
+    }
+
+    /// @returns {string} The name of the function
+    #[wasm_bindgen]
+    pub fn function(&self) -> String {
+        self.function_id.to_string()
+    }
+
+    /// @returns {number} The id of the network the keys were produced for
+    #[wasm_bindgen]
+    pub fn network(&self) -> u16 {
+        self.network
+    }
+
+    /// @returns {string} The version of snarkVM the keys were produced with
+    #[wasm_bindgen(js_name = snarkvmVersion)]
+    pub fn snarkvm_version(&self) -> String {
+        self.snarkvm_version.clone()
+    }
+
+    /// @returns {string} The checksum of the proving key
+    #[wasm_bindgen(js_name = provingKeyChecksum)]
+    pub fn proving_key_checksum(&self) -> String {
+        self.proving_key_checksum.clone()
+    }
+
+    /// @returns {string} The checksum of the verifying key
+    #[wasm_bindgen(js_name = verifyingKeyChecksum)]
+    pub fn verifying_key_checksum(&self) -> String {
+        self.verifying_key_checksum.clone()
+    }
+
+    /// Get the proving key of the bundle
+    ///
+    /// @returns {ProvingKey | Error}
+    #[wasm_bindgen(js_name = provingKey)]
+    pub fn proving_key(&self) -> Result<ProvingKey, String> {
+        ProvingKey::from_bytes(&self.proving_key)
+    }
+
+    /// Get the verifying key of the bundle
+    ///
+    /// @returns {VerifyingKey | Error}
+    #[wasm_bindgen(js_name = verifyingKey)]
+    pub fn verifying_key(&self) -> Result<VerifyingKey, String> {
+        VerifyingKey::from_bytes(&self.verifying_key)
+    }
+
+    /// Get the keys of the bundle as a key pair
+    ///
+    /// @returns {KeyPair | Error}
+    #[wasm_bindgen(js_name = keyPair)]
+    pub fn key_pair(&self) -> Result<KeyPair, String> {
+        Ok(KeyPair::new(self.proving_key()?, self.verifying_key()?))
+    }
+}
+
+impl KeyBundle {
+    const MAGIC: &'static [u8; 8] = b"ALEOKEYS";
+    const VERSION: u8 = 1;
+
+    /// Create a key bundle for the keys of a function
+    pub fn from_native(
+        program_id: ProgramIDNative,
+        function_id: IdentifierNative,
+        proving_key: &ProvingKeyNative,
+        verifying_key: &VerifyingKeyNative,
+    ) -> Result<Self, String> {
+        let proving_key = proving_key.to_bytes_le().map_err(|_| "Failed to serialize proving key".to_string())?;
+        let verifying_key = verifying_key.to_bytes_le().map_err(|_| "Failed to serialize verifying key".to_string())?;
+        Ok(Self {
+            program_id,
+            function_id,
+            network: CurrentNetwork::ID,
+            snarkvm_version: SNARKVM_VERSION.to_string(),
+            proving_key_checksum: key_checksum(&proving_key),
+            verifying_key_checksum: key_checksum(&verifying_key),
+            proving_key,
+            verifying_key,
+        })
+    }
+
+    /// Check that the bundle targets the current network and snarkVM version and that its keys
+    /// match their checksums
+    pub fn verify(&self) -> Result<(), String> {
+        if self.network != CurrentNetwork::ID {
+            return Err(format!(
+                "The key bundle targets network {}, but the SDK uses network {} ({})",
+                self.network,
+                CurrentNetwork::ID,
+                CurrentNetwork::NAME
+            ));
+        }
+        if self.snarkvm_version != SNARKVM_VERSION {
+            return Err(format!(
+                "The key bundle was produced with snarkVM {}, but the SDK uses snarkVM {SNARKVM_VERSION}",
+                self.snarkvm_version
+            ));
+        }
+        for (kind, bytes, checksum) in [
+            ("proving", &self.proving_key, &self.proving_key_checksum),
+            ("verifying", &self.verifying_key, &self.verifying_key_checksum),
+        ] {
+            let actual = key_checksum(bytes);
+            if &actual != checksum {
+                return Err(format!(
+                    "The {kind} key of {}/{} has checksum {actual}, expected {checksum}",
+                    self.program_id, self.function_id
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the program id and function name the keys of the bundle belong to
+    pub fn locator(&self) -> (ProgramIDNative, IdentifierNative) {
+        (self.program_id, self.function_id)
+    }
+
+    /// Deserialize the proving and verifying key of the bundle
+    pub fn native_keys(&self) -> Result<(ProvingKeyNative, VerifyingKeyNative), String> {
+        Ok((
+            ProvingKeyNative::from_bytes_le(&self.proving_key).map_err(|e| e.to_string())?,
+            VerifyingKeyNative::from_bytes_le(&self.verifying_key).map_err(|e| e.to_string())?,
+        ))
+    }
+}
+
+// Write a length prefixed byte string.
+fn write_bytes<W: Write>(bytes: &[u8], mut writer: W) -> IoResult<()> {
+    (bytes.len() as u64).write_le(&mut writer)?;
+    writer.write_all(bytes)
+}
+
+// Read a length prefixed byte string.
+fn read_bytes<R: Read>(mut reader: R) -> IoResult<Vec<u8>> {
+    let length = u64::read_le(&mut reader)?;
+    let mut bytes = vec![];
+    reader.take(length).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != length {
+        return Err(Error::new(ErrorKind::UnexpectedEof, "the file is truncated"));
+    }
+    Ok(bytes)
+}
+
+impl ToBytes for KeyBundle {
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        writer.write_all(Self::MAGIC)?;
+        Self::VERSION.write_le(&mut writer)?;
+        self.network.write_le(&mut writer)?;
+        write_bytes(self.snarkvm_version.as_bytes(), &mut writer)?;
+        self.program_id.write_le(&mut writer)?;
+        self.function_id.write_le(&mut writer)?;
+        for checksum in [&self.proving_key_checksum, &self.verifying_key_checksum] {
+            let checksum = hex::decode(checksum).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            writer.write_all(&checksum)?;
+        }
+        write_bytes(&self.proving_key, &mut writer)?;
+        write_bytes(&self.verifying_key, &mut writer)
+    }
+}
+
+impl FromBytes for KeyBundle {
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(Error::new(ErrorKind::InvalidData, "the file is not a key bundle"));
+        }
+        let version = u8::read_le(&mut reader)?;
+        if version != Self::VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, format!("unsupported key bundle version {version}")));
+        }
+        let network = u16::read_le(&mut reader)?;
+        let snarkvm_version = String::from_utf8(read_bytes(&mut reader)?)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "the snarkVM version is not valid UTF-8"))?;
+        let program_id = ProgramIDNative::read_le(&mut reader)?;
+        let function_id = IdentifierNative::read_le(&mut reader)?;
+        let mut checksums = [[0u8; 32]; 2];
+        for checksum in checksums.iter_mut() {
+            reader.read_exact(checksum)?;
+        }
+        let proving_key = read_bytes(&mut reader)?;
+        let verifying_key = read_bytes(&mut reader)?;
+        Ok(Self {
+            program_id,
+            function_id,
+            network,
+            snarkvm_version,
+            proving_key_checksum: hex::encode(checksums[0]),
+            verifying_key_checksum: hex::encode(checksums[1]),
+            proving_key,
+            verifying_key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::native::{CurrentAleo, ProcessNative, ProgramNative};
+
+    use rand::{rngs::StdRng, SeedableRng};
+    use wasm_bindgen_test::*;
+
+    const PROGRAM: &str = r#"program key_bundle_test.aleo;
+
+function add:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;
+"#;
+
+    fn synthesize_key_pair() -> KeyPair {
+        let program = ProgramNative::from_str(PROGRAM).unwrap();
+        let function_id = IdentifierNative::from_str("add").unwrap();
+        let mut process = ProcessNative::load_web().unwrap();
+        process.add_program(&program).unwrap();
+        process.synthesize_key::<CurrentAleo, _>(program.id(), &function_id, &mut StdRng::from_entropy()).unwrap();
+        let proving_key = process.get_proving_key(program.id(), function_id).unwrap();
+        let verifying_key = process.get_verifying_key(program.id(), function_id).unwrap();
+        KeyPair::from((proving_key, verifying_key))
+    }
+
+    #[wasm_bindgen_test]
+    fn test_key_bundle_round_trip() {
+        let mut key_pair = synthesize_key_pair();
+        let bundle = KeyBundle::from_key_pair("key_bundle_test.aleo", "add", &key_pair).unwrap();
+        assert_eq!(bundle.program_id(), "key_bundle_test.aleo");
+        assert_eq!(bundle.function(), "add");
+        assert_eq!(bundle.network(), CurrentNetwork::ID);
+        assert_eq!(bundle.snarkvm_version(), SNARKVM_VERSION);
+
+        let read = KeyBundle::read(&bundle.write().unwrap()).unwrap();
+        assert_eq!(read, bundle);
+        assert_eq!(read.proving_key_checksum(), key_pair.proving_key().unwrap().checksum());
+        assert_eq!(read.verifying_key().unwrap(), key_pair.verifying_key().unwrap());
+        assert_eq!(read.proving_key().unwrap().checksum(), read.proving_key_checksum());
+        assert!(KeyBundle::from_key_pair("key_bundle_test.aleo", "add", &key_pair).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_key_bundle_integrity() {
+        let bundle = KeyBundle::from_key_pair("key_bundle_test.aleo", "add", &synthesize_key_pair()).unwrap();
+        let bytes = bundle.write().unwrap();
+
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(KeyBundle::read(&corrupted).unwrap_err().contains("checksum"));
+
+        let mut other_network = bytes.clone();
+        other_network[9] ^= 1;
+        assert!(KeyBundle::read(&other_network).unwrap_err().contains("network"));
+
+        let other_version = KeyBundle { snarkvm_version: "0.0.0".to_string(), ..bundle };
+        let error = KeyBundle::read(&other_version.write().unwrap()).unwrap_err();
+        assert!(error.contains("snarkVM 0.0.0") && error.contains(SNARKVM_VERSION), "{error}");
+
+        assert!(KeyBundle::read(&bytes[..bytes.len() - 1]).unwrap_err().contains("truncated"));
+        assert!(KeyBundle::read(&[bytes.as_slice(), &[0]].concat()).unwrap_err().contains("trailing"));
+        assert!(KeyBundle::read(b"not a key bundle").unwrap_err().contains("not a key bundle"));
+    }
+
+    #[wasm_bindgen_test]
+    fn test_snarkvm_version_matches_manifest() {
+        let (version, rev) = SNARKVM_VERSION.split_once('+').unwrap();
+        let manifest = include_str!("../../Cargo.toml");
+        let mut dependencies = 0;
+        for section in manifest.split("\n[").filter(|section| section.starts_with("dependencies.snarkvm-")) {
+            let value = |key: &str| {
+                section
+                    .lines()
+                    .find_map(|line| line.strip_prefix(key).and_then(|line| line.trim().strip_prefix('=')))
+                    .map(|value| value.trim().trim_matches('"'))
+                    .unwrap()
+            };
+            assert_eq!(value("version"), version, "{section}");
+            assert!(value("rev").starts_with(rev), "{section}");
+            dependencies += 1;
+        }
+        assert!(dependencies > 0);
+    }
+}
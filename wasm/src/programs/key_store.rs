@@ -0,0 +1,639 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{program_checksum, KeyPair, ProvingKey, VerifyingKey};
+
+use crate::types::native::{FromBytes, IdentifierNative, ProgramNative, ProvingKeyNative, ToBytes, VerifyingKeyNative};
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use js_sys::{Promise, Uint8Array};
+use sha2::Digest;
+use std::{cell::RefCell, rc::Rc, str::FromStr};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+/// An asynchronous storage backend holding byte entries under string keys. Keys are `/` separated
+/// paths such as `hello.aleo/<program checksum>/main/prover.<checksum>`
+#[async_trait(?Send)]
+pub trait KeyStorage {
+    /// Get the entry stored under a key, if any
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String>;
+
+    /// Store an entry under a key, replacing any existing entry
+    async fn set(&self, key: &str, value: Vec<u8>) -> Result<(), String>;
+
+    /// Remove the entry stored under a key, removing a missing entry is a no-op
+    async fn remove(&self, key: &str) -> Result<(), String>;
+}
+
+/// A key storage backend keeping entries in memory
+#[derive(Clone, Debug, Default)]
+pub struct MemoryKeyStorage {
+    entries: RefCell<IndexMap<String, Vec<u8>>>,
+}
+
+#[async_trait(?Send)]
+impl KeyStorage for MemoryKeyStorage {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        Ok(self.entries.borrow().get(key).cloned())
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>) -> Result<(), String> {
+        self.entries.borrow_mut().insert(key.to_string(), value);
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), String> {
+        self.entries.borrow_mut().shift_remove(key);
+        Ok(())
+    }
+}
+
+/// A key storage backend writing entries as files below a local directory. This backend
+/// requires filesystem access and is only available in native builds
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct DirectoryKeyStorage {
+    directory: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DirectoryKeyStorage {
+    /// Create a key storage backend writing entries below a directory
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into() }
+    }
+
+    // Get the path of the file holding an entry.
+    fn path(&self, key: &str) -> PathBuf {
+        key.split('/').fold(self.directory.clone(), |path, segment| path.join(segment))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait(?Send)]
+impl KeyStorage for DirectoryKeyStorage {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let path = self.path(key);
+        match std::fs::read(&path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>) -> Result<(), String> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+        }
+        std::fs::write(&path, value).map_err(|e| format!("Failed to write {}: {e}", path.display()))
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), String> {
+        let path = self.path(key);
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove {}: {e}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[wasm_bindgen(typescript_custom_section)]
+const KEY_STORAGE_INTERFACE: &str = r#"
+/**
+ * Storage backend of a KeyStore, for instance a wrapper around an IndexedDB object store. Every
+ * method may return a value or a promise of that value
+ */
+export interface KeyStorage {
+    get(key: string): Promise<Uint8Array | undefined> | Uint8Array | undefined;
+    set(key: string, value: Uint8Array): Promise<void> | void;
+    remove(key: string): Promise<void> | void;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A key storage backend implemented in javascript
+    #[wasm_bindgen(typescript_type = "KeyStorage")]
+    pub type JsKeyStorage;
+
+    #[wasm_bindgen(method, catch, js_name = get)]
+    fn get_entry(this: &JsKeyStorage, key: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = set)]
+    fn set_entry(this: &JsKeyStorage, key: &str, value: Uint8Array) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = remove)]
+    fn remove_entry(this: &JsKeyStorage, key: &str) -> Result<JsValue, JsValue>;
+}
+
+// Await the result of a javascript storage call which may or may not return a promise.
+async fn resolve(result: Result<JsValue, JsValue>) -> Result<JsValue, String> {
+    let error = |e: JsValue| e.as_string().unwrap_or_else(|| format!("{e:?}"));
+    JsFuture::from(Promise::resolve(&result.map_err(error)?)).await.map_err(error)
+}
+
+#[async_trait(?Send)]
+impl KeyStorage for JsKeyStorage {
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, String> {
+        let value = resolve(self.get_entry(key)).await?;
+        if value.is_undefined() || value.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(Uint8Array::new(&value).to_vec()))
+    }
+
+    async fn set(&self, key: &str, value: Vec<u8>) -> Result<(), String> {
+        resolve(self.set_entry(key, Uint8Array::from(value.as_slice()))).await.map(|_| ())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), String> {
+        resolve(self.remove_entry(key)).await.map(|_| ())
+    }
+}
+
+/// Get the checksum of serialized key bytes, which matches the checksum() of the key
+pub fn key_checksum(bytes: &[u8]) -> String {
+    hex::encode(sha2::Sha256::digest(bytes))
+}
+
+/// A persistent store of function proving and verifying keys.
+///
+/// The keys of a function are stored under its program id, the checksum of the program source and
+/// the function name, so keys synthesized for an earlier version of a program are never loaded for
+/// a newer one. The key bytes are written under their checksum, for example
+/// `hello.aleo/<program checksum>/main/prover.<checksum>`, next to a `checksums` entry recording
+/// the program checksum and which keys are current. Loaded keys are verified against their recorded
+/// checksum, so a corrupted or partially written entry is never used.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct KeyStore {
+    storage: Rc<dyn KeyStorage>,
+}
+
+thread_local! {
+    // The key store used by the static transaction builders of the program manager.
+    static DEFAULT_KEY_STORE: RefCell<Option<KeyStore>> = const { RefCell::new(None) };
+}
+
+#[wasm_bindgen]
+impl KeyStore {
+    /// Create a key store backed by a javascript storage object, such as a wrapper around IndexedDB
+    ///
+    /// @param {KeyStorage} storage An object with get(key), set(key, bytes) and remove(key)
+    /// methods, which may return promises
+    /// @returns {KeyStore} A key store persisting keys to the storage object
+    #[wasm_bindgen(constructor)]
+    pub fn new(storage: JsKeyStorage) -> KeyStore {
+        Self::from_storage(storage)
+    }
+
+    /// Create a key store keeping keys in memory
+    ///
+    /// @returns {KeyStore} A key store which is discarded along with the program
+    #[wasm_bindgen(js_name = inMemory)]
+    pub fn in_memory() -> KeyStore {
+        Self::from_storage(MemoryKeyStorage::default())
+    }
+
+    /// Use this key store for the static transaction builders of the ProgramManager, such as
+    /// buildExecutionTransaction, buildDeploymentTransaction and buildTransferTransaction. Keys
+    /// which are not passed to a builder are loaded from this key store, and keys synthesized while
+    /// building a transaction are persisted to it
+    #[wasm_bindgen(js_name = setDefault)]
+    pub fn set_default(&self) {
+        DEFAULT_KEY_STORE.with(|store| *store.borrow_mut() = Some(self.clone()));
+    }
+
+    /// Stop using a key store for the static transaction builders of the ProgramManager
+    #[wasm_bindgen(js_name = clearDefault)]
+    pub fn clear_default() {
+        DEFAULT_KEY_STORE.with(|store| *store.borrow_mut() = None);
+    }
+
+    /// Store the proving and verifying key of a function
+    ///
+    /// @param {string} program The source code of the program
+    /// @param {string} function The name of the function
+    /// @param {ProvingKey} proving_key The proving key of the function
+    /// @param {VerifyingKey} verifying_key The verifying key of the function
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(js_name = storeKeys)]
+    pub fn store_keys_js(
+        &self,
+        program: &str,
+        function: &str,
+        proving_key: &ProvingKey,
+        verifying_key: &VerifyingKey,
+    ) -> Promise {
+        let (store, locator) = (self.clone(), parse_locator(program, function));
+        let (proving_key, verifying_key) = (proving_key.clone(), verifying_key.clone());
+        future_to_promise(async move {
+            let (program, function_id) = locator?;
+            store.store_keys(&program, &function_id, &proving_key, &verifying_key).await?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
+    /// Load the proving and verifying key of a function
+    ///
+    /// @param {string} program The source code of the program
+    /// @param {string} function The name of the function
+    /// @returns {Promise<KeyPair | undefined>} The keys of the function, or undefined if no keys
+    /// are stored for this version of the program. The promise is rejected if the stored keys fail
+    /// their checksum
+    #[wasm_bindgen(js_name = loadKeys)]
+    pub fn load_keys_js(&self, program: &str, function: &str) -> Promise {
+        let (store, locator) = (self.clone(), parse_locator(program, function));
+        future_to_promise(async move {
+            let (program, function_id) = locator?;
+            Ok(store
+                .load_keys(&program, &function_id)
+                .await?
+                .map_or(JsValue::UNDEFINED, |keys| JsValue::from(KeyPair::from(keys))))
+        })
+    }
+
+    /// Check whether keys are stored for a function
+    ///
+    /// @param {string} program The source code of the program
+    /// @param {string} function The name of the function
+    /// @returns {Promise<boolean>} True if keys are stored for the function of this version of the
+    /// program
+    #[wasm_bindgen(js_name = hasKeys)]
+    pub fn has_keys_js(&self, program: &str, function: &str) -> Promise {
+        let (store, locator) = (self.clone(), parse_locator(program, function));
+        future_to_promise(async move {
+            let (program, function_id) = locator?;
+            Ok(JsValue::from_bool(store.contains_keys(&program, &function_id).await?))
+        })
+    }
+
+    /// Remove the stored keys of a function
+    ///
+    /// @param {string} program The source code of the program
+    /// @param {string} function The name of the function
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(js_name = removeKeys)]
+    pub fn remove_keys_js(&self, program: &str, function: &str) -> Promise {
+        let (store, locator) = (self.clone(), parse_locator(program, function));
+        future_to_promise(async move {
+            let (program, function_id) = locator?;
+            store.remove_keys(&program, &function_id).await?;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+}
+
+impl KeyStore {
+    /// Get the key store used by the static transaction builders, if one was set with setDefault
+    pub fn default_store() -> Option<KeyStore> {
+        DEFAULT_KEY_STORE.with(|store| store.borrow().clone())
+    }
+
+    /// Create a key store backed by any key storage backend
+    pub fn from_storage(storage: impl KeyStorage + 'static) -> Self {
+        Self { storage: Rc::new(storage) }
+    }
+
+    /// Create a key store writing keys as files below a local directory
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_directory(directory: impl Into<PathBuf>) -> Self {
+        Self::from_storage(DirectoryKeyStorage::new(directory))
+    }
+
+    /// Store the proving and verifying key of a function, replacing any keys already stored for
+    /// the same version of the program
+    pub async fn store_keys(
+        &self,
+        program: &ProgramNative,
+        function_id: &IdentifierNative,
+        proving_key: &ProvingKeyNative,
+        verifying_key: &VerifyingKeyNative,
+    ) -> Result<(), String> {
+        let proving_key = proving_key.to_bytes_le().map_err(|_| "Failed to serialize proving key".to_string())?;
+        let verifying_key = verifying_key.to_bytes_le().map_err(|_| "Failed to serialize verifying key".to_string())?;
+        let checksums = StoredChecksums {
+            program: program_checksum(program)?,
+            prover: key_checksum(&proving_key),
+            verifier: key_checksum(&verifying_key),
+        };
+        let previous = self.checksums(program, function_id).await?;
+
+        // Write the key bytes before the checksums so readers never see a partially stored entry
+        let locator = locator(program, function_id)?;
+        self.storage.set(&format!("{locator}/prover.{}", checksums.prover), proving_key).await?;
+        self.storage.set(&format!("{locator}/verifier.{}", checksums.verifier), verifying_key).await?;
+        self.storage.set(&format!("{locator}/checksums"), checksums.to_bytes()).await?;
+
+        if let Some(previous) = previous {
+            if previous.prover != checksums.prover {
+                self.storage.remove(&format!("{locator}/prover.{}", previous.prover)).await?;
+            }
+            if previous.verifier != checksums.verifier {
+                self.storage.remove(&format!("{locator}/verifier.{}", previous.verifier)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the proving and verifying key of a function, returning None if no keys are stored for
+    /// this version of the program. An error is returned if the stored keys are missing, do not
+    /// match their checksum or were stored for a program with a different checksum
+    pub async fn load_keys(
+        &self,
+        program: &ProgramNative,
+        function_id: &IdentifierNative,
+    ) -> Result<Option<(ProvingKeyNative, VerifyingKeyNative)>, String> {
+        let checksums = match self.checksums(program, function_id).await? {
+            Some(checksums) => checksums,
+            None => return Ok(None),
+        };
+        let locator = locator(program, function_id)?;
+        let proving_key = self.load_verified(&locator, "prover", &checksums.prover).await?;
+        let verifying_key = self.load_verified(&locator, "verifier", &checksums.verifier).await?;
+        Ok(Some((
+            ProvingKeyNative::from_bytes_le(&proving_key).map_err(|e| e.to_string())?,
+            VerifyingKeyNative::from_bytes_le(&verifying_key).map_err(|e| e.to_string())?,
+        )))
+    }
+
+    /// Check whether keys are stored for a function of this version of a program without loading
+    /// them
+    pub async fn contains_keys(&self, program: &ProgramNative, function_id: &IdentifierNative) -> Result<bool, String> {
+        Ok(self.checksums(program, function_id).await?.is_some())
+    }
+
+    /// Get the checksums of the proving and verifying key stored for a function
+    pub async fn stored_checksums(
+        &self,
+        program: &ProgramNative,
+        function_id: &IdentifierNative,
+    ) -> Result<Option<(String, String)>, String> {
+        Ok(self.checksums(program, function_id).await?.map(|checksums| (checksums.prover, checksums.verifier)))
+    }
+
+    /// Remove the stored keys of a function of this version of a program
+    pub async fn remove_keys(&self, program: &ProgramNative, function_id: &IdentifierNative) -> Result<(), String> {
+        if let Some(checksums) = self.checksums(program, function_id).await? {
+            let locator = locator(program, function_id)?;
+            self.storage.remove(&format!("{locator}/checksums")).await?;
+            self.storage.remove(&format!("{locator}/prover.{}", checksums.prover)).await?;
+            self.storage.remove(&format!("{locator}/verifier.{}", checksums.verifier)).await?;
+        }
+        Ok(())
+    }
+
+    // Read the checksums entry of a function, rejecting entries recorded for another program.
+    async fn checksums(
+        &self,
+        program: &ProgramNative,
+        function_id: &IdentifierNative,
+    ) -> Result<Option<StoredChecksums>, String> {
+        let locator = locator(program, function_id)?;
+        let checksums = match self.storage.get(&format!("{locator}/checksums")).await? {
+            Some(bytes) => StoredChecksums::from_bytes(&bytes)
+                .ok_or_else(|| format!("The stored checksums of {locator} are corrupted"))?,
+            None => return Ok(None),
+        };
+        let expected = program_checksum(program)?;
+        if checksums.program != expected {
+            return Err(format!(
+                "The keys stored for {}/{function_id} belong to program checksum {}, expected {expected}",
+                program.id(),
+                checksums.program
+            ));
+        }
+        Ok(Some(checksums))
+    }
+
+    // Read key bytes and verify them against their checksum.
+    async fn load_verified(&self, locator: &str, kind: &str, checksum: &str) -> Result<Vec<u8>, String> {
+        let bytes = self
+            .storage
+            .get(&format!("{locator}/{kind}.{checksum}"))
+            .await?
+            .ok_or_else(|| format!("The stored {kind} key of {locator} is missing"))?;
+        let actual = key_checksum(&bytes);
+        if actual != checksum {
+            return Err(format!("The stored {kind} key of {locator} has checksum {actual}, expected {checksum}"));
+        }
+        Ok(bytes)
+    }
+}
+
+// The checksums of the program and keys stored for a function, serialized as three lines of hex.
+struct StoredChecksums {
+    program: String,
+    prover: String,
+    verifier: String,
+}
+
+impl StoredChecksums {
+    fn to_bytes(&self) -> Vec<u8> {
+        format!("{}\n{}\n{}", self.program, self.prover, self.verifier).into_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut lines = std::str::from_utf8(bytes).ok()?.split('\n');
+        let (program, prover, verifier) = (lines.next()?, lines.next()?, lines.next()?);
+        let is_checksum = |checksum: &str| checksum.len() == 64 && checksum.chars().all(|c| c.is_ascii_hexdigit());
+        (lines.next().is_none() && is_checksum(program) && is_checksum(prover) && is_checksum(verifier)).then(|| Self {
+            program: program.to_string(),
+            prover: prover.to_string(),
+            verifier: verifier.to_string(),
+        })
+    }
+}
+
+// Get the storage path of the keys of a function of a specific version of a program.
+fn locator(program: &ProgramNative, function_id: &IdentifierNative) -> Result<String, String> {
+    Ok(format!("{}/{}/{function_id}", program.id(), program_checksum(program)?))
+}
+
+// Parse the program and function passed from javascript.
+fn parse_locator(program: &str, function: &str) -> Result<(ProgramNative, IdentifierNative), String> {
+    Ok((
+        ProgramNative::from_str(program).map_err(|e| e.to_string())?,
+        IdentifierNative::from_str(function).map_err(|e| e.to_string())?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::native::{CurrentAleo, ProcessNative};
+
+    use rand::{rngs::StdRng, SeedableRng};
+    use wasm_bindgen_test::*;
+
+    const PROGRAM: &str = r#"program key_store_test.aleo;
+
+function add:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;
+"#;
+
+    fn synthesize_keys() -> (ProgramNative, IdentifierNative, ProvingKeyNative, VerifyingKeyNative) {
+        let program = ProgramNative::from_str(PROGRAM).unwrap();
+        let function_id = IdentifierNative::from_str("add").unwrap();
+        let mut process = ProcessNative::load_web().unwrap();
+        process.add_program(&program).unwrap();
+        process.synthesize_key::<CurrentAleo, _>(program.id(), &function_id, &mut StdRng::from_entropy()).unwrap();
+        let proving_key = process.get_proving_key(program.id(), function_id).unwrap();
+        let verifying_key = process.get_verifying_key(program.id(), function_id).unwrap();
+        (program, function_id, proving_key, verifying_key)
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_store_and_load_keys() {
+        let (program, function_id, proving_key, verifying_key) = synthesize_keys();
+        let store = KeyStore::in_memory();
+
+        assert!(store.load_keys(&program, &function_id).await.unwrap().is_none());
+        assert!(!store.contains_keys(&program, &function_id).await.unwrap());
+
+        store.store_keys(&program, &function_id, &proving_key, &verifying_key).await.unwrap();
+        assert!(store.contains_keys(&program, &function_id).await.unwrap());
+
+        let (loaded_proving_key, loaded_verifying_key) =
+            store.load_keys(&program, &function_id).await.unwrap().unwrap();
+        assert_eq!(ProvingKey::from(loaded_proving_key).checksum(), ProvingKey::from(proving_key.clone()).checksum());
+        assert_eq!(VerifyingKey::from(loaded_verifying_key), VerifyingKey::from(verifying_key.clone()));
+
+        let (prover, verifier) = store.stored_checksums(&program, &function_id).await.unwrap().unwrap();
+        assert_eq!(prover, ProvingKey::from(proving_key).checksum());
+        assert_eq!(verifier, VerifyingKey::from(verifying_key).checksum());
+
+        store.remove_keys(&program, &function_id).await.unwrap();
+        assert!(store.load_keys(&program, &function_id).await.unwrap().is_none());
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_corrupted_keys_are_rejected() {
+        let (program, function_id, proving_key, verifying_key) = synthesize_keys();
+        let storage = Rc::new(MemoryKeyStorage::default());
+        let store = KeyStore { storage: storage.clone() };
+        store.store_keys(&program, &function_id, &proving_key, &verifying_key).await.unwrap();
+
+        let (prover, _) = store.stored_checksums(&program, &function_id).await.unwrap().unwrap();
+        let key = format!("{}/prover.{prover}", locator(&program, &function_id).unwrap());
+        let mut bytes = storage.get(&key).await.unwrap().unwrap();
+        bytes[0] ^= 1;
+        storage.set(&key, bytes).await.unwrap();
+
+        let error = store.load_keys(&program, &function_id).await.unwrap_err();
+        assert!(error.contains("expected"), "{error}");
+
+        storage.remove(&key).await.unwrap();
+        let error = store.load_keys(&program, &function_id).await.unwrap_err();
+        assert!(error.contains("missing"), "{error}");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_keys_of_other_program_versions_are_rejected() {
+        let (program, function_id, proving_key, verifying_key) = synthesize_keys();
+        let storage = Rc::new(MemoryKeyStorage::default());
+        let store = KeyStore { storage: storage.clone() };
+        store.store_keys(&program, &function_id, &proving_key, &verifying_key).await.unwrap();
+
+        // Keys stored for one version of a program are not visible to an updated version
+        let updated = ProgramNative::from_str(&PROGRAM.replace("add r0 r1", "add r1 r0")).unwrap();
+        assert!(!store.contains_keys(&updated, &function_id).await.unwrap());
+        assert!(store.load_keys(&updated, &function_id).await.unwrap().is_none());
+
+        // A checksums entry recording another program checksum is never loaded
+        let locator = locator(&program, &function_id).unwrap();
+        let checksums = storage.get(&format!("{locator}/checksums")).await.unwrap().unwrap();
+        let checksums = StoredChecksums::from_bytes(&checksums).unwrap();
+        let tampered = StoredChecksums { program: program_checksum(&updated).unwrap(), ..checksums };
+        storage.set(&format!("{locator}/checksums"), tampered.to_bytes()).await.unwrap();
+
+        let error = store.load_keys(&program, &function_id).await.unwrap_err();
+        assert!(error.contains("program checksum"), "{error}");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_js_key_storage() {
+        // Entries are kept in a Map, with get answering through a promise and set synchronously
+        let entries = js_sys::Map::new();
+        let storage = js_sys::Function::new_with_args(
+            "entries",
+            "return { get: async (key) => entries.get(key), set: (key, value) => { entries.set(key, value) }, remove: async (key) => { entries.delete(key) } }",
+        );
+        let storage = JsKeyStorage::from(storage.call1(&JsValue::NULL, &entries).unwrap());
+
+        assert!(storage.get("a/b").await.unwrap().is_none());
+        storage.set("a/b", vec![1, 2, 3]).await.unwrap();
+        assert_eq!(storage.get("a/b").await.unwrap(), Some(vec![1, 2, 3]));
+        assert!(entries.has(&JsValue::from_str("a/b")));
+        storage.remove("a/b").await.unwrap();
+        assert!(storage.get("a/b").await.unwrap().is_none());
+
+        // Keys round trip through the javascript storage
+        let (program, function_id, proving_key, verifying_key) = synthesize_keys();
+        let store = KeyStore::from_storage(storage);
+        store.store_keys(&program, &function_id, &proving_key, &verifying_key).await.unwrap();
+        let (_, loaded_verifying_key) = store.load_keys(&program, &function_id).await.unwrap().unwrap();
+        assert_eq!(VerifyingKey::from(loaded_verifying_key), VerifyingKey::from(verifying_key));
+
+        // Errors thrown or rejected by the storage are reported
+        let failing = js_sys::Function::new_no_args(
+            "return { get: async () => { throw 'unavailable' }, set: () => { throw 'read only' }, remove: () => {} }",
+        );
+        let failing = JsKeyStorage::from(failing.call0(&JsValue::NULL).unwrap());
+        assert_eq!(failing.get("a/b").await.unwrap_err(), "unavailable");
+        assert_eq!(failing.set("a/b", vec![1]).await.unwrap_err(), "read only");
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_directory_key_storage() {
+        use futures::FutureExt;
+
+        let directory = std::env::temp_dir().join(format!("aleo_key_store_{}", rand::random::<u64>()));
+        let storage = DirectoryKeyStorage::new(&directory);
+
+        // Entries are written as files below the directory, creating parent directories as needed
+        assert!(storage.get("a/b/c").now_or_never().unwrap().unwrap().is_none());
+        storage.set("a/b/c", vec![1, 2, 3]).now_or_never().unwrap().unwrap();
+        assert_eq!(std::fs::read(directory.join("a").join("b").join("c")).unwrap(), vec![1, 2, 3]);
+        assert_eq!(storage.get("a/b/c").now_or_never().unwrap().unwrap(), Some(vec![1, 2, 3]));
+
+        // Removing an entry twice is a no-op
+        storage.remove("a/b/c").now_or_never().unwrap().unwrap();
+        storage.remove("a/b/c").now_or_never().unwrap().unwrap();
+        assert!(storage.get("a/b/c").now_or_never().unwrap().unwrap().is_none());
+
+        // Keys round trip through the directory
+        let (program, function_id, proving_key, verifying_key) = synthesize_keys();
+        let store = KeyStore::from_directory(&directory);
+        store.store_keys(&program, &function_id, &proving_key, &verifying_key).now_or_never().unwrap().unwrap();
+        let (_, loaded_verifying_key) =
+            store.load_keys(&program, &function_id).now_or_never().unwrap().unwrap().unwrap();
+        assert_eq!(VerifyingKey::from(loaded_verifying_key), VerifyingKey::from(verifying_key));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
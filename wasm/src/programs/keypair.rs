@@ -56,6 +56,16 @@ impl KeyPair {
     }
 }
 
+impl KeyPair {
+    /// Borrow both keys without removing them, failing if either has already been removed
+    pub fn keys(&self) -> Result<(&ProvingKey, &VerifyingKey), String> {
+        match (&self.proving_key, &self.verifying_key) {
+            (Some(proving_key), Some(verifying_key)) => Ok((proving_key, verifying_key)),
+            _ => Err("The keys have already been removed from the key pair".to_string()),
+        }
+    }
+}
+
 impl From<(ProvingKeyNative, VerifyingKeyNative)> for KeyPair {
     fn from((proving_key, verifying_key): (ProvingKeyNative, VerifyingKeyNative)) -> Self {
         Self::new(proving_key.into(), verifying_key.into())
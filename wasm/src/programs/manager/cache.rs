@@ -0,0 +1,608 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use crate::{log, ExecutionResponse, FunctionKeys, OfflineQuery, PrivateKey, ProgramKeys, RecordPlaintext};
+
+use crate::types::native::{CurrentAleo, IdentifierNative, ProcessNative, ProgramIDNative, ProgramNative, ToBytes};
+use js_sys::{Array, Object, Promise, Reflect};
+use rand::{rngs::StdRng, SeedableRng};
+use std::str::FromStr;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::future_to_promise;
+
+#[wasm_bindgen]
+impl ProgramManager {
+    /// Create a program manager which keeps programs and keys cached in a long-lived process
+    ///
+    /// @returns {ProgramManager | Error} A program manager whose process contains only credits.aleo
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Result<ProgramManager, String> {
+        Ok(Self {
+            process: ProcessNative::load_web().map_err(|err| err.to_string())?,
+            programs: IndexMap::new(),
+            key_sizes: IndexMap::new(),
+            key_store: None,
+        })
+    }
+
+    /// Use a key store for the keys of this program manager. Before a function is executed, the
+    /// keys it needs which are not cached are loaded from the key store, and keys synthesized
+    /// during the execution are persisted to it
+    ///
+    /// @param {KeyStore} key_store The key store to load and persist keys with
+    #[wasm_bindgen(js_name = setKeyStore)]
+    pub fn set_key_store(&mut self, key_store: &KeyStore) {
+        self.key_store = Some(key_store.clone());
+    }
+
+    /// Add a program and its imports to the program manager. Adding a program which is already
+    /// cached is a no-op, while adding a different program with the same id is an error
+    ///
+    /// @param {string} program The source code of the program
+    /// @param {Object | undefined} imports (optional) The imports of the program which are not cached
+    /// yet, in the form \{ "hello.aleo": "hello.aleo source code" \}
+    /// @returns {string | Error} The id of the program
+    #[wasm_bindgen(js_name = addProgram)]
+    pub fn add_program(&mut self, program: &str, imports: Option<Object>) -> Result<String, String> {
+        let program = ProgramNative::from_str(program).map_err(|err| err.to_string())?;
+        if let Ok(cached) = self.process.get_program(program.id()) {
+            if cached != &program {
+                return Err(format!(
+                    "A different version of {} is already cached, evict it before adding this program",
+                    program.id()
+                ));
+            }
+            return Ok(program.id().to_string());
+        }
+
+        Self::resolve_imports(&mut self.process, &program, imports)?;
+        self.process.add_program(&program).map_err(|err| err.to_string())?;
+        self.track_program(&program)?;
+        Ok(program.id().to_string())
+    }
+
+    /// Check whether a program is cached by the program manager
+    ///
+    /// @param {string} program_id The id of the program
+    /// @returns {boolean} True if the program is cached
+    #[wasm_bindgen(js_name = hasProgram)]
+    pub fn has_program(&self, program_id: &str) -> bool {
+        ProgramIDNative::from_str(program_id).map_or(false, |program_id| self.process.contains_program(&program_id))
+    }
+
+    /// Get the ids of the programs added to the program manager, including their imports
+    ///
+    /// @returns {Array} The ids of the cached programs in the order they were added
+    #[wasm_bindgen(js_name = getPrograms)]
+    pub fn get_programs(&self) -> Array {
+        self.programs.keys().map(|program_id| JsValue::from_str(&program_id.to_string())).collect()
+    }
+
+    /// Insert a proving and verifying key for a function of a cached program, replacing any keys
+    /// already cached for the function
+    ///
+    /// @param {string} program_id The id of the program
+    /// @param {string} function The name of the function
+    /// @param {ProvingKey} proving_key The proving key of the function
+    /// @param {VerifyingKey} verifying_key The verifying key of the function
+    #[wasm_bindgen(js_name = insertKeys)]
+    pub fn insert_keys(
+        &mut self,
+        program_id: &str,
+        function: &str,
+        proving_key: ProvingKey,
+        verifying_key: VerifyingKey,
+    ) -> Result<(), String> {
+        let (program_id, function_id) = self.function_locator(program_id, function)?;
+        self.process
+            .insert_proving_key(&program_id, &function_id, ProvingKeyNative::from(proving_key))
+            .map_err(|err| err.to_string())?;
+        self.process
+            .insert_verifying_key(&program_id, &function_id, VerifyingKeyNative::from(verifying_key))
+            .map_err(|err| err.to_string())?;
+        self.key_sizes.shift_remove(&(program_id, function_id));
+        Ok(())
+    }
+
+    /// Synthesize the proving and verifying key of a function of a cached program without
+    /// executing it. Keys which are already cached are returned without being synthesized again
+    ///
+    /// @param {string} program_id The id of the program
+    /// @param {string} function The name of the function
+    /// @returns {KeyPair | Error} The keys of the function
+    #[wasm_bindgen(js_name = synthesizeKeys)]
+    pub fn synthesize_keys(&mut self, program_id: &str, function: &str) -> Result<KeyPair, String> {
+        let (program_id, function_id) = self.function_locator(program_id, function)?;
+        if !Self::contains_key(&self.process, &program_id, &function_id) {
+            log(&format!("Synthesizing keys for {program_id}/{function_id}"));
+            self.process
+                .synthesize_key::<CurrentAleo, _>(&program_id, &function_id, &mut StdRng::from_entropy())
+                .map_err(|err| err.to_string())?;
+        }
+        let proving_key = self.process.get_proving_key(program_id, function_id).map_err(|err| err.to_string())?;
+        let verifying_key = self.process.get_verifying_key(program_id, function_id).map_err(|err| err.to_string())?;
+        Ok(KeyPair::from((proving_key, verifying_key)))
+    }
+
+    /// Synthesize the proving and verifying keys of every function of a cached program and of the
+    /// programs it imports, without requiring inputs for the functions. Keys which are already
+    /// cached are reused, and the keys of imported credits.aleo functions, which are published on
+    /// the parameter server, are not included
+    ///
+    /// @param {string} program_id The id of the program
+    /// @returns {ProgramKeys | Error} The keys and constraint counts of every function, with the
+    /// functions of imported programs first
+    #[wasm_bindgen(js_name = synthesizeProgramKeys)]
+    pub fn synthesize_program_keys(&mut self, program_id: &str) -> Result<ProgramKeys, String> {
+        let program = self.cached_program(program_id)?;
+
+        // Programs are tracked after their imports, so walking them backwards finds every dependency
+        let mut dependencies = vec![*program.id()];
+        for (id, tracked) in self.programs.iter().rev() {
+            if dependencies.contains(id) {
+                dependencies.extend(tracked.imports().keys().copied());
+            }
+        }
+        let mut programs =
+            self.programs.values().filter(|tracked| dependencies.contains(tracked.id())).cloned().collect::<Vec<_>>();
+        if programs.is_empty() {
+            programs.push(program);
+        }
+
+        let mut functions = vec![];
+        for program in programs.iter() {
+            for function_id in program.functions().keys() {
+                if !Self::contains_key(&self.process, program.id(), function_id) {
+                    log(&format!("Synthesizing keys for {}/{function_id}", program.id()));
+                    self.process
+                        .synthesize_key::<CurrentAleo, _>(program.id(), function_id, &mut StdRng::from_entropy())
+                        .map_err(|err| err.to_string())?;
+                }
+                let proving_key =
+                    self.process.get_proving_key(*program.id(), *function_id).map_err(|err| err.to_string())?;
+                let verifying_key =
+                    self.process.get_verifying_key(*program.id(), *function_id).map_err(|err| err.to_string())?;
+                functions.push(FunctionKeys::new(*program.id(), *function_id, proving_key, verifying_key));
+            }
+        }
+        Ok(ProgramKeys::new(functions))
+    }
+
+    /// Make the keys needed to execute a function of a cached program available ahead of an
+    /// execution, loading them from the key store or synthesizing and persisting them
+    ///
+    /// @param {string} program_id The id of the program
+    /// @param {string} function The name of the function
+    /// @returns {Promise<void>}
+    #[wasm_bindgen(js_name = prepareKeys)]
+    pub fn prepare_keys(&self, program_id: &str, function: &str) -> Promise {
+        // The cloned process shares its stacks, and therefore its keys, with this program manager
+        let mut manager = self.clone();
+        let locator = self.function_locator(program_id, function);
+        future_to_promise(async move {
+            let (program_id, function_id) = locator?;
+            let functions = Self::required_functions(&manager.process, program_id, function_id, None)?;
+            let missing = Self::load_stored_keys(&manager.process, manager.key_store.as_ref(), &functions).await?;
+            for (program_id, function_id) in missing.iter() {
+                log(&format!("Synthesizing keys for {program_id}/{function_id}"));
+                manager
+                    .process
+                    .synthesize_key::<CurrentAleo, _>(program_id, function_id, &mut StdRng::from_entropy())
+                    .map_err(|err| err.to_string())?;
+            }
+            Self::persist_keys(&manager.process, manager.key_store.as_ref(), &missing).await;
+            Ok(JsValue::UNDEFINED)
+        })
+    }
+
+    /// Execute a function of a cached program. Keys synthesized during the execution are cached,
+    /// so later executions of the same function skip synthesis
+    ///
+    /// @param {PrivateKey} private_key The private key of the caller
+    /// @param {string} program_id The id of the cached program
+    /// @param {string} function The name of the function to execute
+    /// @param {Array} inputs A javascript array of inputs to the function, as Aleo strings or javascript values
+    /// @param {boolean} prove_execution If true, the execution will be proven
+    /// @param {string | undefined} url (optional) The url of the Aleo network node used to prepare
+    /// inclusion proofs
+    /// @param {OfflineQuery | undefined} offline_query (optional) An offline query used to prepare
+    /// inclusion proofs instead of the network
+    /// @returns {Promise<ExecutionResponse>} The response of the execution
+    #[wasm_bindgen(js_name = executeFunction)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn execute_function(
+        &self,
+        private_key: &PrivateKey,
+        program_id: &str,
+        function: &str,
+        inputs: Array,
+        prove_execution: bool,
+        url: Option<String>,
+        offline_query: Option<OfflineQuery>,
+    ) -> Promise {
+        // The cloned process shares its stacks, and therefore its keys, with this program manager
+        let mut manager = self.clone();
+        let (private_key, program_id, function) = (private_key.clone(), program_id.to_string(), function.to_string());
+        future_to_promise(async move {
+            let response = manager
+                .execute_cached(&private_key, &program_id, &function, &inputs, prove_execution, url, offline_query)
+                .await?;
+            Ok(JsValue::from(response))
+        })
+    }
+
+    /// Build an execution transaction for a function of a cached program, reusing the cached keys
+    /// of both the function and the fee
+    ///
+    /// @param {PrivateKey} private_key The private key of the sender
+    /// @param {string} program_id The id of the cached program
+    /// @param {string} function The name of the function to execute
+    /// @param {Array} inputs A javascript array of inputs to the function, as Aleo strings or javascript values
+    /// @param {bigint | string | number} fee_credits The fee to pay, as a bigint of microcredits, a
+    /// decimal string of credits or (deprecated) a number of credits
+    /// @param {RecordPlaintext | undefined} fee_record (optional) The record to spend the fee from
+    /// @param {string | undefined} url (optional) The url of the Aleo network node
+    /// @param {OfflineQuery | undefined} offline_query (optional) An offline query used to prepare
+    /// inclusion proofs instead of the network
+    /// @param {bigint | string | number | undefined} priority_fee_credits (optional) The priority fee
+    /// to pay on top of the base fee, as a bigint of microcredits, a decimal string of credits or
+    /// (deprecated) a number of credits
+    /// @returns {Promise<Transaction>} The execution transaction
+    #[wasm_bindgen(js_name = buildExecution)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_execution(
+        &self,
+        private_key: &PrivateKey,
+        program_id: &str,
+        function: &str,
+        inputs: Array,
+        fee_credits: JsValue,
+        fee_record: Option<RecordPlaintext>,
+        url: Option<String>,
+        offline_query: Option<OfflineQuery>,
+        priority_fee_credits: JsValue,
+    ) -> Promise {
+        let fees = Self::validate_fees(&fee_credits, &priority_fee_credits, fee_record.as_ref());
+        // The cloned process shares its stacks, and therefore its keys, with this program manager
+        let mut manager = self.clone();
+        let (private_key, program_id, function) = (private_key.clone(), program_id.to_string(), function.to_string());
+        future_to_promise(async move {
+            let (fee_microcredits, priority_fee_microcredits) = fees?;
+            let program = manager.cached_program(&program_id)?;
+            let (program_id, function_id) = manager.function_locator(&program_id, &function)?;
+            let fee = if fee_record.is_some() { "private" } else { "public" };
+            let functions = Self::required_functions(&manager.process, program_id, function_id, Some(fee))?;
+            let missing = Self::load_stored_keys(&manager.process, manager.key_store.as_ref(), &functions).await?;
+
+            let transaction = Self::build_execution_in_process(
+                &mut manager.process,
+                &private_key,
+                &program,
+                &function,
+                &inputs,
+                fee_microcredits,
+                priority_fee_microcredits,
+                fee_record,
+                url,
+                None,
+                None,
+                None,
+                None,
+                offline_query,
+            )
+            .await?;
+            Self::persist_keys(&manager.process, manager.key_store.as_ref(), &missing).await;
+            Ok(JsValue::from(transaction))
+        })
+    }
+
+    /// Remove the cached keys of a function, or of every function of a program if no function is
+    /// specified
+    ///
+    /// @param {string} program_id The id of the program
+    /// @param {string | undefined} function (optional) The name of the function
+    #[wasm_bindgen(js_name = evictKeys)]
+    pub fn evict_keys(&mut self, program_id: &str, function: Option<String>) -> Result<(), String> {
+        let program_id = ProgramIDNative::from_str(program_id).map_err(|err| err.to_string())?;
+        let stack = self.process.get_stack(program_id).map_err(|err| err.to_string())?;
+        let functions = match function {
+            Some(function) => vec![IdentifierNative::from_str(&function).map_err(|err| err.to_string())?],
+            None => stack.program().functions().keys().copied().collect(),
+        };
+        for function_id in functions {
+            stack.remove_proving_key(&function_id);
+            stack.remove_verifying_key(&function_id);
+            self.key_sizes.shift_remove(&(program_id, function_id));
+        }
+        Ok(())
+    }
+
+    /// Remove a program from the program manager along with its keys and every cached program
+    /// which imports it
+    ///
+    /// @param {string} program_id The id of the program
+    #[wasm_bindgen(js_name = evictProgram)]
+    pub fn evict_program(&mut self, program_id: &str) -> Result<(), String> {
+        let program_id = ProgramIDNative::from_str(program_id).map_err(|err| err.to_string())?;
+        if !self.programs.contains_key(&program_id) {
+            return Err(format!("Program {program_id} is not cached"));
+        }
+
+        // Programs are tracked after their imports, so dependants of an evicted program follow it
+        let mut evicted = vec![program_id];
+        for (id, program) in self.programs.iter() {
+            if program.imports().keys().any(|import| evicted.contains(import)) {
+                evicted.push(*id);
+            }
+        }
+
+        // Rebuild the process from the remaining programs, keeping their keys
+        let retained = self.cached_keys().into_iter().filter(|(id, _)| !evicted.contains(id)).collect::<Vec<_>>();
+        let mut process = ProcessNative::load_web().map_err(|err| err.to_string())?;
+        for (id, program) in self.programs.iter() {
+            if !evicted.contains(id) {
+                process.add_program(program).map_err(|err| err.to_string())?;
+            }
+        }
+        for (id, function_id) in retained {
+            let proving_key = self.process.get_proving_key(id, function_id).map_err(|err| err.to_string())?;
+            let verifying_key = self.process.get_verifying_key(id, function_id).map_err(|err| err.to_string())?;
+            process.insert_proving_key(&id, &function_id, proving_key).map_err(|err| err.to_string())?;
+            process.insert_verifying_key(&id, &function_id, verifying_key).map_err(|err| err.to_string())?;
+        }
+
+        self.process = process;
+        self.programs.retain(|id, _| !evicted.contains(id));
+        self.key_sizes.retain(|(id, _), _| !evicted.contains(id));
+        Ok(())
+    }
+
+    /// Remove every cached program and key. Keys persisted to the key store are kept
+    #[wasm_bindgen]
+    pub fn clear(&mut self) -> Result<(), String> {
+        let key_store = self.key_store.take();
+        *self = Self::new()?;
+        self.key_store = key_store;
+        Ok(())
+    }
+
+    /// Report the programs and keys cached by the program manager and the memory used by the keys
+    ///
+    /// @returns {Object | Error} An object of the form \{ programs: Array<string>, keys: Array, provingKeyBytes: number,
+    /// verifyingKeyBytes: number \} where each key entry has the form \{ programId: string, function: string,
+    /// provingKeyBytes: number, verifyingKeyBytes: number \}
+    #[wasm_bindgen(js_name = memoryUsage)]
+    pub fn memory_usage(&mut self) -> Result<Object, String> {
+        let keys = Array::new();
+        let (mut proving_key_bytes, mut verifying_key_bytes) = (0usize, 0usize);
+        for (program_id, function_id) in self.cached_keys() {
+            let (proving, verifying) = self.cached_key_sizes(program_id, function_id)?;
+            proving_key_bytes += proving;
+            verifying_key_bytes += verifying;
+
+            let key = Object::new();
+            Reflect::set(&key, &"programId".into(), &JsValue::from_str(&program_id.to_string()))
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&key, &"function".into(), &JsValue::from_str(&function_id.to_string()))
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&key, &"provingKeyBytes".into(), &JsValue::from(proving as f64))
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&key, &"verifyingKeyBytes".into(), &JsValue::from(verifying as f64))
+                .map_err(|_| "Failed to set property")?;
+            keys.push(&key);
+        }
+
+        let usage = Object::new();
+        Reflect::set(&usage, &"programs".into(), &self.get_programs().into()).map_err(|_| "Failed to set property")?;
+        Reflect::set(&usage, &"keys".into(), &keys.into()).map_err(|_| "Failed to set property")?;
+        Reflect::set(&usage, &"provingKeyBytes".into(), &JsValue::from(proving_key_bytes as f64))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&usage, &"verifyingKeyBytes".into(), &JsValue::from(verifying_key_bytes as f64))
+            .map_err(|_| "Failed to set property")?;
+        Ok(usage)
+    }
+}
+
+impl ProgramManager {
+    /// Execute a function of a cached program in the program manager's process, loading missing
+    /// keys from the key store and persisting the keys synthesized during the execution
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute_cached(
+        &mut self,
+        private_key: &PrivateKey,
+        program_id: &str,
+        function: &str,
+        inputs: &Array,
+        prove_execution: bool,
+        url: Option<String>,
+        offline_query: Option<OfflineQuery>,
+    ) -> Result<ExecutionResponse, String> {
+        log(&format!("Executing cached function: {program_id}/{function}"));
+        let program = self.cached_program(program_id)?;
+        let (program_id, function_id) = self.function_locator(program_id, function)?;
+        let functions = Self::required_functions(&self.process, program_id, function_id, None)?;
+        let missing = Self::load_stored_keys(&self.process, self.key_store.as_ref(), &functions).await?;
+        let response = Self::execute_in_process(
+            &mut self.process,
+            private_key,
+            &program,
+            function,
+            inputs,
+            prove_execution,
+            None,
+            None,
+            url,
+            offline_query,
+        )
+        .await?;
+        Self::persist_keys(&self.process, self.key_store.as_ref(), &missing).await;
+        Ok(response)
+    }
+
+    /// Load the keys of the given functions which are not in the process from a key store,
+    /// returning the functions whose keys remain to be synthesized. Stored keys failing their
+    /// checksum are ignored so they are synthesized and persisted again
+    pub(crate) async fn load_stored_keys(
+        process: &ProcessNative,
+        key_store: Option<&KeyStore>,
+        functions: &[(ProgramIDNative, IdentifierNative)],
+    ) -> Result<Vec<(ProgramIDNative, IdentifierNative)>, String> {
+        let mut missing = vec![];
+        for (program_id, function_id) in functions {
+            if Self::contains_key(process, program_id, function_id) {
+                continue;
+            }
+            let program = process.get_program(program_id).map_err(|err| err.to_string())?;
+            let stored = match key_store {
+                Some(key_store) => key_store.load_keys(program, function_id).await.unwrap_or_else(|err| {
+                    log(&format!("Ignoring stored keys of {program_id}/{function_id}: {err}"));
+                    None
+                }),
+                None => None,
+            };
+            match stored {
+                Some((proving_key, verifying_key)) => {
+                    log(&format!("Loaded stored keys for {program_id}/{function_id}"));
+                    process.insert_proving_key(program_id, function_id, proving_key).map_err(|err| err.to_string())?;
+                    process
+                        .insert_verifying_key(program_id, function_id, verifying_key)
+                        .map_err(|err| err.to_string())?;
+                }
+                None => missing.push((*program_id, *function_id)),
+            }
+        }
+        Ok(missing)
+    }
+
+    /// Persist the keys of the given functions which are now in the process to a key store.
+    /// Failing to persist keys does not fail the operation which synthesized them
+    pub(crate) async fn persist_keys(
+        process: &ProcessNative,
+        key_store: Option<&KeyStore>,
+        functions: &[(ProgramIDNative, IdentifierNative)],
+    ) {
+        let key_store = match key_store {
+            Some(key_store) => key_store,
+            None => return,
+        };
+        for (program_id, function_id) in functions {
+            if !Self::contains_key(process, program_id, function_id) {
+                continue;
+            }
+            let keys = process.get_program(program_id).and_then(|program| {
+                let proving_key = process.get_proving_key(*program_id, *function_id)?;
+                Ok((program, proving_key, process.get_verifying_key(*program_id, *function_id)?))
+            });
+            let result = match keys {
+                Ok((program, proving_key, verifying_key)) => {
+                    key_store.store_keys(program, function_id, &proving_key, &verifying_key).await
+                }
+                Err(err) => Err(err.to_string()),
+            };
+            if let Err(err) = result {
+                log(&format!("Failed to persist keys for {program_id}/{function_id}: {err}"));
+            }
+        }
+    }
+
+    /// Load the keys needed by a static transaction builder from the default key store, returning
+    /// the functions whose keys remain to be synthesized. Functions whose keys were passed to the
+    /// builder are skipped so the passed keys are used
+    pub(crate) async fn load_default_keys(
+        process: &ProcessNative,
+        functions: &[(ProgramIDNative, IdentifierNative)],
+        passed: &[(ProgramIDNative, IdentifierNative)],
+    ) -> Result<Vec<(ProgramIDNative, IdentifierNative)>, String> {
+        let functions = functions.iter().filter(|function| !passed.contains(function)).copied().collect::<Vec<_>>();
+        Self::load_stored_keys(process, KeyStore::default_store().as_ref(), &functions).await
+    }
+
+    /// Persist the keys synthesized by a static transaction builder to the default key store
+    pub(crate) async fn persist_default_keys(
+        process: &ProcessNative,
+        functions: &[(ProgramIDNative, IdentifierNative)],
+    ) {
+        Self::persist_keys(process, KeyStore::default_store().as_ref(), functions).await
+    }
+
+    // Get a cached program, credits.aleo is always cached.
+    fn cached_program(&self, program_id: &str) -> Result<ProgramNative, String> {
+        let program_id = ProgramIDNative::from_str(program_id).map_err(|err| err.to_string())?;
+        self.process
+            .get_program(program_id)
+            .cloned()
+            .map_err(|_| format!("Program {program_id} is not cached, add it with addProgram first"))
+    }
+
+    // Parse the locator of a function of a cached program.
+    pub(crate) fn function_locator(
+        &self,
+        program_id: &str,
+        function: &str,
+    ) -> Result<(ProgramIDNative, IdentifierNative), String> {
+        let program = self.cached_program(program_id)?;
+        let function_id =
+            IdentifierNative::from_str(function).map_err(|_| "The function name provided was invalid".to_string())?;
+        if !program.contains_function(&function_id) {
+            return Err(format!("Program {} does not contain the function {function_id}", program.id()));
+        }
+        Ok((*program.id(), function_id))
+    }
+
+    // Track a program added to the process along with any imports which are not tracked yet.
+    fn track_program(&mut self, program: &ProgramNative) -> Result<(), String> {
+        for import_id in program.imports().keys() {
+            if !self.programs.contains_key(import_id) && &import_id.to_string() != "credits.aleo" {
+                let import = self.process.get_program(import_id).map_err(|err| err.to_string())?.clone();
+                self.track_program(&import)?;
+            }
+        }
+        self.programs.insert(*program.id(), program.clone());
+        Ok(())
+    }
+
+    // Get the serialized sizes of the cached keys of a function, measuring them on first use.
+    pub(crate) fn cached_key_sizes(
+        &mut self,
+        program_id: ProgramIDNative,
+        function_id: IdentifierNative,
+    ) -> Result<(usize, usize), String> {
+        if let Some(sizes) = self.key_sizes.get(&(program_id, function_id)) {
+            return Ok(*sizes);
+        }
+        let proving_key = self.process.get_proving_key(program_id, function_id).map_err(|err| err.to_string())?;
+        let verifying_key = self.process.get_verifying_key(program_id, function_id).map_err(|err| err.to_string())?;
+        let sizes = (
+            proving_key.to_bytes_le().map_err(|err| err.to_string())?.len(),
+            verifying_key.to_bytes_le().map_err(|err| err.to_string())?.len(),
+        );
+        self.key_sizes.insert((program_id, function_id), sizes);
+        Ok(sizes)
+    }
+
+    // Get the functions of credits.aleo and of the tracked programs which have cached keys.
+    fn cached_keys(&self) -> Vec<(ProgramIDNative, IdentifierNative)> {
+        let credits = ProgramNative::credits().ok();
+        credits
+            .iter()
+            .chain(self.programs.values())
+            .flat_map(|program| program.functions().keys().map(move |function_id| (*program.id(), *function_id)))
+            .filter(|(program_id, function_id)| Self::contains_key(&self.process, program_id, function_id))
+            .collect()
+    }
+}
@@ -16,25 +16,29 @@
 
 use super::*;
 
-use crate::{execute_fee, log, OfflineQuery, PrivateKey, RecordPlaintext, Transaction};
+use crate::{execute_fee, log, DeploymentCostBreakdown, OfflineQuery, PrivateKey, RecordPlaintext, Transaction};
 
 use crate::types::native::{
     CurrentAleo,
     CurrentNetwork,
+    DeploymentNative,
+    IdentifierNative,
     ProcessNative,
     ProgramIDNative,
     ProgramNative,
     ProgramOwnerNative,
     RecordPlaintextNative,
     TransactionNative,
+    VerifyingKeyNative,
 };
-use js_sys::Object;
+use js_sys::{Object, Reflect};
 use rand::{rngs::StdRng, SeedableRng};
 use std::str::FromStr;
 
 #[wasm_bindgen]
 impl ProgramManager {
-    /// Deploy an Aleo program
+    /// Deploy an Aleo program. Fee keys which are not passed are loaded from the default key store
+    /// if one is set with `KeyStore.setDefault`
     ///
     /// @param private_key The private key of the sender
     /// @param program The source code of the program being deployed
@@ -42,7 +46,8 @@ impl ProgramManager {
     /// form \{"program_name1": "program_source_code", "program_name2": "program_source_code", ..\}.
     /// Note that all imported programs must be deployed on chain before the main program in order
     /// for the deployment to succeed
-    /// @param fee_credits The amount of credits to pay as a fee
+    /// @param fee_credits The fee to pay, as a bigint of microcredits, a decimal string of credits or
+    /// (deprecated) a number of credits
     /// @param fee_record The record to spend the fee from
     /// @param url The url of the Aleo network node to send the transaction to
     /// @param imports (optional) Provide a list of imports to use for the program deployment in the
@@ -50,26 +55,32 @@ impl ProgramManager {
     /// are a string representing the program source code \{ "hello.aleo": "hello.aleo source code" \}
     /// @param fee_proving_key (optional) Provide a proving key to use for the fee execution
     /// @param fee_verifying_key (optional) Provide a verifying key to use for the fee execution
+    /// @param priority_fee_credits (optional) The priority fee to pay on top of the base fee, as a
+    /// bigint of microcredits, a decimal string of credits or (deprecated) a number of credits. If a
+    /// fee record is provided, it must hold enough credits to pay both fees
+    /// @param import_checksums (optional) The checksums to pin imports to, as returned by
+    /// `Program.checksum`, in the form \{ "hello.aleo": "checksum" \}. Imports whose checksum differs
+    /// are rejected before any keys are synthesized
     /// @returns {Transaction | Error}
     #[wasm_bindgen(js_name = buildDeploymentTransaction)]
     #[allow(clippy::too_many_arguments)]
     pub async fn deploy(
         private_key: &PrivateKey,
         program: &str,
-        fee_credits: f64,
+        fee_credits: JsValue,
         fee_record: Option<RecordPlaintext>,
         url: Option<String>,
         imports: Option<Object>,
         fee_proving_key: Option<ProvingKey>,
         fee_verifying_key: Option<VerifyingKey>,
         offline_query: Option<OfflineQuery>,
+        priority_fee_credits: JsValue,
+        import_checksums: Option<Object>,
     ) -> Result<Transaction, String> {
         log("Creating deployment transaction");
         // Convert fee to microcredits and check that the fee record has enough credits to pay it
-        let fee_microcredits = match &fee_record {
-            Some(fee_record) => Self::validate_amount(fee_credits, fee_record, true)?,
-            None => (fee_credits * 1_000_000.0) as u64,
-        };
+        let (fee_microcredits, priority_fee_microcredits) =
+            Self::validate_fees(&fee_credits, &priority_fee_credits, fee_record.as_ref())?;
 
         let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
         let process = &mut process_native;
@@ -78,7 +89,7 @@ impl ProgramManager {
         let program = ProgramNative::from_str(program).map_err(|err| err.to_string())?;
 
         log("Checking program imports are valid and add them to the process");
-        ProgramManager::resolve_imports(process, &program, imports)?;
+        ProgramManager::resolve_pinned_imports(process, &program, imports, import_checksums)?;
         let rng = &mut StdRng::from_entropy();
 
         log("Creating deployment");
@@ -100,11 +111,17 @@ impl ProgramManager {
 
         let deployment_id = deployment.to_deployment_id().map_err(|e| e.to_string())?;
 
+        log("Loading stored fee keys from the default key store");
+        let fee_locator = Self::fee_locator(fee_record.is_some())?;
+        let passed = if fee_proving_key.is_some() { vec![fee_locator] } else { vec![] };
+        let missing = Self::load_default_keys(process, &[fee_locator], &passed).await?;
+
         let fee = execute_fee!(
             process,
             private_key,
             fee_record,
             fee_microcredits,
+            priority_fee_microcredits,
             node_url,
             fee_proving_key,
             fee_verifying_key,
@@ -121,6 +138,7 @@ impl ProgramManager {
         process
             .verify_deployment::<CurrentAleo, _>(&deployment, &mut StdRng::from_entropy())
             .map_err(|err| err.to_string())?;
+        Self::persist_default_keys(process, &missing).await;
 
         log("Creating deployment transaction");
         Ok(Transaction::from(
@@ -142,21 +160,7 @@ impl ProgramManager {
         log(
             "Disclaimer: Fee estimation is experimental and may not represent a correct estimate on any current or future network",
         );
-        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
-        let process = &mut process_native;
-
-        log("Check program has a valid name");
-        let program = ProgramNative::from_str(program).map_err(|err| err.to_string())?;
-
-        log("Check program imports are valid and add them to the process");
-        ProgramManager::resolve_imports(process, &program, imports)?;
-
-        log("Create sample deployment");
-        let deployment =
-            process.deploy::<CurrentAleo, _>(&program, &mut StdRng::from_entropy()).map_err(|err| err.to_string())?;
-        if deployment.program().functions().is_empty() {
-            return Err("Attempted to create an empty transaction deployment".to_string());
-        }
+        let deployment = Self::sample_deployment(program, imports)?;
 
         log("Estimate the deployment fee");
         let (minimum_deployment_cost, (_, _, _)) =
@@ -165,6 +169,29 @@ impl ProgramManager {
         Ok(minimum_deployment_cost)
     }
 
+    /// Estimate the cost of a program deployment itemized by component
+    ///
+    /// Disclaimer: Fee estimation is experimental and may not represent a correct estimate on any current or future network
+    ///
+    /// @param program The source code of the program being deployed
+    /// @param imports (optional) Provide a list of imports to use for the deployment cost estimation
+    /// in the form of a javascript object where the keys are a string of the program name and the values
+    /// are a string representing the program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @returns {Object | Error} An object of the form \{ storageBytes: number, storageCost: number,
+    /// synthesisCost: number, namespaceCost: number, totalCost: number, functions: Array \} where costs are in
+    /// microcredits and each function is of the form \{ function: string, constraints: number, variables: number,
+    /// synthesisCost: number \}
+    #[wasm_bindgen(js_name = estimateDeploymentCost)]
+    pub async fn estimate_deployment_cost(program: &str, imports: Option<Object>) -> Result<Object, String> {
+        log(
+            "Disclaimer: Fee estimation is experimental and may not represent a correct estimate on any current or future network",
+        );
+        let deployment = Self::sample_deployment(program, imports)?;
+
+        log("Itemize the deployment cost");
+        DeploymentCostBreakdown::from_deployment(&deployment)?.to_object()
+    }
+
     /// Estimate the component of the deployment cost which comes from the fee for the program name.
     /// Note that this cost does not represent the entire cost of deployment. It is additional to
     /// the cost of the size (in bytes) of the deployment.
@@ -185,4 +212,113 @@ impl ProgramManager {
             .saturating_mul(1_000_000); // 1 microcredit = 1e-6 credits.
         Ok(namespace_cost)
     }
+
+    /// Synthesize the verifying key of a function locally and compare it with the verifying key
+    /// deployed on chain, confirming that a deployed program matches its published source
+    ///
+    /// @param {string} program The source code of the program
+    /// @param {string} function The name of the function
+    /// @param {string} deployed The deployment transaction of the program, or the deployed
+    /// verifying key of the function as a string
+    /// @param {Object | undefined} imports (optional) The imports of the program in the form
+    /// \{ "hello.aleo": "hello.aleo source code" \}
+    /// @returns {Object | Error} An object of the form \{ identical: boolean, localChecksum: string,
+    /// deployedChecksum: string, programMatches: boolean | undefined \}, where programMatches
+    /// reports whether the program in a deployment transaction equals the given source
+    #[wasm_bindgen(js_name = verifyDeployedKey)]
+    pub fn verify_deployed_key(
+        program: &str,
+        function: &str,
+        deployed: &str,
+        imports: Option<Object>,
+    ) -> Result<Object, String> {
+        let program = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
+        let function_id =
+            IdentifierNative::from_str(function).map_err(|_| "The function name provided was invalid".to_string())?;
+        if !program.contains_function(&function_id) {
+            return Err(format!("Program {} does not contain the function {function_id}", program.id()));
+        }
+        let (deployed_key, program_matches) = Self::deployed_verifying_key(&program, &function_id, deployed)?;
+
+        log(&format!("Synthesizing the verifying key of {}/{function_id}", program.id()));
+        let mut process = ProcessNative::load_web().map_err(|e| e.to_string())?;
+        if !process.contains_program(program.id()) {
+            Self::resolve_imports(&mut process, &program, imports)?;
+            process.add_program(&program).map_err(|e| e.to_string())?;
+        }
+        process
+            .synthesize_key::<CurrentAleo, _>(program.id(), &function_id, &mut StdRng::from_entropy())
+            .map_err(|e| e.to_string())?;
+        let local_key = process.get_verifying_key(program.id(), function_id).map_err(|e| e.to_string())?;
+
+        let local_checksum = VerifyingKey::from(local_key).checksum();
+        let deployed_checksum = VerifyingKey::from(deployed_key).checksum();
+        let result = Object::new();
+        Reflect::set(&result, &"identical".into(), &JsValue::from_bool(local_checksum == deployed_checksum))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&result, &"localChecksum".into(), &JsValue::from_str(&local_checksum))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&result, &"deployedChecksum".into(), &JsValue::from_str(&deployed_checksum))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(
+            &result,
+            &"programMatches".into(),
+            &program_matches.map_or(JsValue::UNDEFINED, JsValue::from_bool),
+        )
+        .map_err(|_| "Failed to set property")?;
+        Ok(result)
+    }
+}
+
+impl ProgramManager {
+    /// Synthesize the keys of a program in order to measure the size and cost of its deployment
+    pub(crate) fn sample_deployment(program: &str, imports: Option<Object>) -> Result<DeploymentNative, String> {
+        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
+        let process = &mut process_native;
+
+        log("Check program has a valid name");
+        let program = ProgramNative::from_str(program).map_err(|err| err.to_string())?;
+
+        log("Check program imports are valid and add them to the process");
+        ProgramManager::resolve_imports(process, &program, imports)?;
+
+        log("Create sample deployment");
+        let deployment =
+            process.deploy::<CurrentAleo, _>(&program, &mut StdRng::from_entropy()).map_err(|err| err.to_string())?;
+        if deployment.program().functions().is_empty() {
+            return Err("Attempted to create an empty transaction deployment".to_string());
+        }
+        Ok(deployment)
+    }
+
+    // Find the verifying key of a function in a deployment transaction, along with whether the
+    // deployed program equals the given program, or parse a verifying key string.
+    fn deployed_verifying_key(
+        program: &ProgramNative,
+        function_id: &IdentifierNative,
+        deployed: &str,
+    ) -> Result<(VerifyingKeyNative, Option<bool>), String> {
+        let transaction = match TransactionNative::from_str(deployed) {
+            Ok(transaction) => transaction,
+            Err(_) => {
+                let verifying_key = VerifyingKeyNative::from_str(deployed).map_err(|_| {
+                    "The deployed key must be a deployment transaction or a verifying key string".to_string()
+                })?;
+                return Ok((verifying_key, None));
+            }
+        };
+        let deployment = match &transaction {
+            TransactionNative::Deploy(_, _, deployment, _) => deployment,
+            _ => return Err(format!("Transaction {} is not a deployment", transaction.id())),
+        };
+        if deployment.program_id() != program.id() {
+            return Err(format!("The transaction deploys {}, not {}", deployment.program_id(), program.id()));
+        }
+        let (_, (verifying_key, _)) = deployment
+            .verifying_keys()
+            .iter()
+            .find(|(id, _)| id == function_id)
+            .ok_or_else(|| format!("The deployment of {} has no verifying key for {function_id}", program.id()))?;
+        Ok((verifying_key.clone(), Some(deployment.program() == program)))
+    }
 }
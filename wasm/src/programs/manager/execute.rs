@@ -18,10 +18,13 @@ use super::*;
 use core::ops::Add;
 
 use crate::{
+    encode_inputs,
     execute_fee,
     execute_program,
     log,
-    process_inputs,
+    validate_inputs,
+    Address,
+    ExecutionCostBreakdown,
     ExecutionResponse,
     OfflineQuery,
     PrivateKey,
@@ -31,6 +34,7 @@ use crate::{
 
 use crate::types::native::{
     CurrentAleo,
+    ExecutionNative,
     IdentifierNative,
     ProcessNative,
     ProgramNative,
@@ -48,7 +52,9 @@ impl ProgramManager {
     /// @param {PrivateKey} private_key The private key of the sender
     /// @param {string} program The source code of the program being executed
     /// @param {string} function The name of the function to execute
-    /// @param {Array} inputs A javascript array of inputs to the function
+    /// @param {Array} inputs A javascript array of inputs to the function. Inputs can be Aleo strings or
+    /// javascript values (numbers, bigints, booleans, objects, arrays and `RecordPlaintext` instances),
+    /// which are converted using the input types declared by the function
     /// @param {boolean} prove_execution If true, the execution will be proven and an execution object
     /// containing the proof and the encrypted inputs and outputs needed to verify the proof offline
     /// will be returned.
@@ -77,10 +83,6 @@ impl ProgramManager {
         offline_query: Option<OfflineQuery>,
     ) -> Result<ExecutionResponse, String> {
         log(&format!("Executing local function: {function}"));
-        let node_url = url.as_deref().unwrap_or(DEFAULT_URL);
-        let inputs = inputs.to_vec();
-        let rng = &mut StdRng::from_entropy();
-
         let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
         let process = &mut process_native;
 
@@ -88,33 +90,19 @@ impl ProgramManager {
         let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
         ProgramManager::resolve_imports(process, &program_native, imports)?;
 
-        let (response, mut trace) = execute_program!(
+        let mut execution_response = Self::execute_in_process(
             process,
-            process_inputs!(inputs),
-            program,
-            function,
             private_key,
+            &program_native,
+            function,
+            &inputs,
+            prove_execution,
             proving_key,
             verifying_key,
-            rng
-        );
-
-        let mut execution_response = if prove_execution {
-            log("Preparing inclusion proofs for execution");
-            if let Some(offline_query) = offline_query {
-                trace.prepare_async(offline_query).await.map_err(|err| err.to_string())?;
-            } else {
-                let query = QueryNative::from(node_url);
-                trace.prepare_async(query).await.map_err(|err| err.to_string())?;
-            }
-
-            log("Proving execution");
-            let locator = program_native.id().to_string().add("/").add(function);
-            let execution = trace.prove_execution::<CurrentAleo, _>(&locator, rng).map_err(|e| e.to_string())?;
-            ExecutionResponse::new(Some(execution), function, response, process, program)?
-        } else {
-            ExecutionResponse::new(None, function, response, process, program)?
-        };
+            url,
+            offline_query,
+        )
+        .await?;
 
         if cache {
             execution_response.add_proving_key(process, function, program_native.id())?;
@@ -123,13 +111,103 @@ impl ProgramManager {
         Ok(execution_response)
     }
 
-    /// Execute Aleo function and create an Aleo execution transaction
+    /// Evaluate an arbitrary function locally without synthesizing circuits or proving keys. This
+    /// is much faster than an execution and is intended for previewing the outputs of a function
+    /// and for testing programs. The response contains the outputs and futures of the function but
+    /// no execution and no keys.
+    ///
+    /// @param {PrivateKey} private_key The private key of the caller
+    /// @param {string} program The source code of the program being evaluated
+    /// @param {string} function The name of the function to evaluate
+    /// @param {Array} inputs A javascript array of inputs to the function, as Aleo strings or javascript values
+    /// @param {Object | undefined} imports (optional) Provide a list of imports to use for the function evaluation in the
+    /// form of a javascript object where the keys are a string of the program name and the values
+    /// are a string representing the program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @returns {ExecutionResponse | Error} The response containing the outputs of the function
+    #[wasm_bindgen(js_name = evaluateFunctionOffline)]
+    pub async fn evaluate_function_offline(
+        private_key: &PrivateKey,
+        program: &str,
+        function: &str,
+        inputs: Array,
+        imports: Option<Object>,
+    ) -> Result<ExecutionResponse, String> {
+        log(&format!("Evaluating local function: {function}"));
+        let rng = &mut StdRng::from_entropy();
+
+        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
+        let process = &mut process_native;
+
+        log("Check program imports are valid and add them to the process");
+        let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
+        ProgramManager::resolve_imports(process, &program_native, imports)?;
+        if !process.contains_program(program_native.id()) {
+            process.add_program(&program_native).map_err(|e| e.to_string())?;
+        }
+
+        let inputs = ProgramManager::encode_function_inputs(&program_native, function, &inputs)?;
+        ProgramManager::check_inputs(process, &program_native, function, &inputs, private_key)?;
+
+        let function_name =
+            IdentifierNative::from_str(function).map_err(|_| "The function name provided was invalid".to_string())?;
+        let authorization = process
+            .authorize::<CurrentAleo, _>(private_key, program_native.id(), function_name, inputs.iter(), rng)
+            .map_err(|err| err.to_string())?;
+        let response = process.evaluate::<CurrentAleo>(authorization).map_err(|err| err.to_string())?;
+
+        ExecutionResponse::evaluated(function, response, program_native)
+    }
+
+    /// Validate the inputs of a function call against the input types declared by the function
+    /// without synthesizing keys or executing the function
+    ///
+    /// @param {string} program The source code of the program containing the function
+    /// @param {string} function The name of the function
+    /// @param {Array} inputs A javascript array of inputs to the function, as Aleo strings or javascript values
+    /// @param {Object | undefined} imports (optional) Provide a list of imports used to resolve external
+    /// records in the form of a javascript object where the keys are a string of the program name and
+    /// the values are a string representing the program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @param {Address | undefined} caller (optional) The address executing the function. If provided,
+    /// record inputs are checked to be owned by this address
+    /// @returns {Array | Error} An array of diagnostics of the form
+    /// \{ index: number, register: string, expected: string, message: string \}, empty if all inputs are valid
+    #[wasm_bindgen(js_name = validateInputs)]
+    pub fn validate_function_inputs(
+        program: &str,
+        function: &str,
+        inputs: Array,
+        imports: Option<Object>,
+        caller: Option<Address>,
+    ) -> Result<Array, String> {
+        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
+        let process = &mut process_native;
+
+        let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
+        ProgramManager::resolve_imports(process, &program_native, imports)?;
+        let function_id =
+            IdentifierNative::from_str(function).map_err(|_| "The function name provided was invalid".to_string())?;
+
+        let inputs = encode_inputs(&program_native, &function_id, &inputs)?;
+        let diagnostics =
+            validate_inputs(process, &program_native, &function_id, &inputs, caller.as_ref().map(|caller| &**caller))?;
+        let array = Array::new_with_length(diagnostics.len() as u32);
+        for (index, diagnostic) in diagnostics.iter().enumerate() {
+            array.set(index as u32, diagnostic.to_object()?.into());
+        }
+        Ok(array)
+    }
+
+    /// Execute Aleo function and create an Aleo execution transaction. Keys which are not passed
+    /// are loaded from the default key store if one is set with `KeyStore.setDefault`
     ///
     /// @param private_key The private key of the sender
     /// @param program The source code of the program being executed
     /// @param function The name of the function to execute
-    /// @param inputs A javascript array of inputs to the function
-    /// @param fee_credits The amount of credits to pay as a fee
+    /// @param inputs A javascript array of inputs to the function. Inputs can be Aleo strings or
+    /// javascript values (numbers, bigints, booleans, objects, arrays and `RecordPlaintext` instances),
+    /// which are converted using the input types declared by the function
+    /// @param fee_credits The fee to pay, as a bigint of microcredits, a decimal string of credits or
+    /// (deprecated) a number of credits
     /// @param fee_record The record to spend the fee from
     /// @param url The url of the Aleo network node to send the transaction to
     /// If this is set to 'true' the keys synthesized (or passed in as optional parameters via the
@@ -143,6 +221,12 @@ impl ProgramManager {
     /// @param verifying_key (optional) Provide a verifying key to use for the function execution
     /// @param fee_proving_key (optional) Provide a proving key to use for the fee execution
     /// @param fee_verifying_key (optional) Provide a verifying key to use for the fee execution
+    /// @param priority_fee_credits (optional) The priority fee to pay on top of the base fee, as a
+    /// bigint of microcredits, a decimal string of credits or (deprecated) a number of credits. If a
+    /// fee record is provided, it must hold enough credits to pay both fees
+    /// @param import_checksums (optional) The checksums to pin imports to, as returned by
+    /// `Program.checksum`, in the form \{ "hello.aleo": "checksum" \}. Imports whose checksum differs
+    /// are rejected before any keys are synthesized
     /// @returns {Transaction | Error}
     #[wasm_bindgen(js_name = buildExecutionTransaction)]
     #[allow(clippy::too_many_arguments)]
@@ -151,7 +235,7 @@ impl ProgramManager {
         program: &str,
         function: &str,
         inputs: Array,
-        fee_credits: f64,
+        fee_credits: JsValue,
         fee_record: Option<RecordPlaintext>,
         url: Option<String>,
         imports: Option<Object>,
@@ -160,69 +244,55 @@ impl ProgramManager {
         fee_proving_key: Option<ProvingKey>,
         fee_verifying_key: Option<VerifyingKey>,
         offline_query: Option<OfflineQuery>,
+        priority_fee_credits: JsValue,
+        import_checksums: Option<Object>,
     ) -> Result<Transaction, String> {
         log(&format!("Executing function: {function} on-chain"));
-        let fee_microcredits = match &fee_record {
-            Some(fee_record) => Self::validate_amount(fee_credits, fee_record, true)?,
-            None => (fee_credits * 1_000_000.0) as u64,
-        };
+        let (fee_microcredits, priority_fee_microcredits) =
+            Self::validate_fees(&fee_credits, &priority_fee_credits, fee_record.as_ref())?;
         let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
         let process = &mut process_native;
-        let node_url = url.as_deref().unwrap_or(DEFAULT_URL);
 
         log("Check program imports are valid and add them to the process");
         let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
-        ProgramManager::resolve_imports(process, &program_native, imports)?;
-        let rng = &mut StdRng::from_entropy();
-
-        log("Executing program");
-        let (_, mut trace) = execute_program!(
-            process,
-            process_inputs!(inputs),
-            program,
-            function,
-            private_key,
-            proving_key,
-            verifying_key,
-            rng
-        );
-
-        log("Preparing inclusion proofs for execution");
-        if let Some(offline_query) = offline_query.as_ref() {
-            trace.prepare_async(offline_query.clone()).await.map_err(|err| err.to_string())?;
-        } else {
-            let query = QueryNative::from(node_url);
-            trace.prepare_async(query).await.map_err(|err| err.to_string())?;
+        ProgramManager::resolve_pinned_imports(process, &program_native, imports, import_checksums)?;
+        if !process.contains_program(program_native.id()) {
+            process.add_program(&program_native).map_err(|e| e.to_string())?;
         }
 
-        log("Proving execution");
-        let program = ProgramNative::from_str(program).map_err(|err| err.to_string())?;
-        let locator = program.id().to_string().add("/").add(function);
-        let execution = trace
-            .prove_execution::<CurrentAleo, _>(&locator, &mut StdRng::from_entropy())
-            .map_err(|e| e.to_string())?;
-        let execution_id = execution.to_execution_id().map_err(|e| e.to_string())?;
+        log("Loading stored keys from the default key store");
+        let function_id =
+            IdentifierNative::from_str(function).map_err(|_| "The function name provided was invalid".to_string())?;
+        let fee = if fee_record.is_some() { "private" } else { "public" };
+        let functions = Self::required_functions(process, *program_native.id(), function_id, Some(fee))?;
+        let mut passed = vec![];
+        if proving_key.is_some() {
+            passed.push((*program_native.id(), function_id));
+        }
+        if fee_proving_key.is_some() {
+            passed.push(Self::fee_locator(fee_record.is_some())?);
+        }
+        let missing = Self::load_default_keys(process, &functions, &passed).await?;
 
-        log("Executing fee");
-        let fee = execute_fee!(
+        let transaction = Self::build_execution_in_process(
             process,
             private_key,
-            fee_record,
+            &program_native,
+            function,
+            &inputs,
             fee_microcredits,
-            node_url,
+            priority_fee_microcredits,
+            fee_record,
+            url,
+            proving_key,
+            verifying_key,
             fee_proving_key,
             fee_verifying_key,
-            execution_id,
-            rng,
-            offline_query
-        );
-
-        // Verify the execution
-        process.verify_execution(&execution).map_err(|err| err.to_string())?;
-
-        log("Creating execution transaction");
-        let transaction = TransactionNative::from_execution(execution, Some(fee)).map_err(|err| err.to_string())?;
-        Ok(Transaction::from(transaction))
+            offline_query,
+        )
+        .await?;
+        Self::persist_default_keys(process, &missing).await;
+        Ok(transaction)
     }
 
     /// Estimate Fee for Aleo function execution. Note if "cache" is set to true, the proving and
@@ -258,62 +328,159 @@ impl ProgramManager {
         log(
             "Disclaimer: Fee estimation is experimental and may not represent a correct estimate on any current or future network",
         );
-        log(&format!("Executing local function: {function}"));
-
-        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
-        let process = &mut process_native;
+        let (process, execution) = Self::prove_sample_execution(
+            private_key,
+            program,
+            function,
+            inputs,
+            url,
+            imports,
+            proving_key,
+            verifying_key,
+            offline_query,
+        )
+        .await?;
 
-        log("Check program imports are valid and add them to the process");
-        let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
-        ProgramManager::resolve_imports(process, &program_native, imports)?;
-        let rng = &mut StdRng::from_entropy();
+        log("Estimating cost");
+        Ok(ExecutionCostBreakdown::from_execution(&process, &execution, None)?.total_cost)
+    }
 
-        log("Generating execution trace");
-        let (_, mut trace) = execute_program!(
-            process,
-            process_inputs!(inputs),
+    /// Estimate the cost of an Aleo function execution itemized by transition. For executions
+    /// calling functions of imported programs this shows which callee dominates the cost.
+    ///
+    /// Disclaimer: Fee estimation is experimental and may not represent a correct estimate on any current or future network
+    ///
+    /// @param private_key The private key of the sender
+    /// @param program The source code of the program to estimate the execution cost for
+    /// @param function The name of the function to execute
+    /// @param inputs A javascript array of inputs to the function
+    /// @param url The url of the Aleo network node used to prepare the inclusion proofs
+    /// @param imports (optional) Provide a list of imports to use for the cost estimation in the
+    /// form of a javascript object where the keys are a string of the program name and the values
+    /// are a string representing the program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @param proving_key (optional) Provide a proving key to use for the cost estimation
+    /// @param verifying_key (optional) Provide a verifying key to use for the cost estimation
+    /// @returns {Object | Error} An object of the form \{ executionBytes: number, proofBytes: number,
+    /// storageCost: number, finalizeCost: number, totalCost: number, feeBytes: null, transitions: Array \}
+    /// where costs are in microcredits and each transition is of the form \{ program: string,
+    /// function: string, bytes: number, finalizeCost: number \}
+    #[wasm_bindgen(js_name = estimateExecutionCost)]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn estimate_execution_cost(
+        private_key: &PrivateKey,
+        program: &str,
+        function: &str,
+        inputs: Array,
+        url: Option<String>,
+        imports: Option<Object>,
+        proving_key: Option<ProvingKey>,
+        verifying_key: Option<VerifyingKey>,
+        offline_query: Option<OfflineQuery>,
+    ) -> Result<Object, String> {
+        log(
+            "Disclaimer: Fee estimation is experimental and may not represent a correct estimate on any current or future network",
+        );
+        let (process, execution) = Self::prove_sample_execution(
+            private_key,
             program,
             function,
-            private_key,
+            inputs,
+            url,
+            imports,
             proving_key,
             verifying_key,
-            rng
+            offline_query,
+        )
+        .await?;
+
+        log("Itemizing cost");
+        ExecutionCostBreakdown::from_execution(&process, &execution, None)?.to_object()
+    }
+
+    /// Estimate the cost of an Aleo function execution without proving it or fetching state paths.
+    /// The function is authorized to compute its transitions, the size of the proof is approximated
+    /// and the finalize costs are added, which makes the estimate near instant.
+    ///
+    /// Disclaimer: Fee estimation is experimental and may not represent a correct estimate on any current or future network
+    ///
+    /// @param private_key The private key of the sender
+    /// @param program The source code of the program to estimate the execution cost for
+    /// @param function The name of the function to execute
+    /// @param inputs A javascript array of inputs to the function, as Aleo strings or javascript values
+    /// @param imports (optional) Provide a list of imports to use for the cost estimation in the
+    /// form of a javascript object where the keys are a string of the program name and the values
+    /// are a string representing the program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @returns {Object | Error} The itemized cost in the same form as `estimateExecutionCost`, where
+    /// `proofBytes` is an approximation
+    #[wasm_bindgen(js_name = estimateExecutionCostWithoutProof)]
+    pub fn estimate_execution_cost_without_proof(
+        private_key: &PrivateKey,
+        program: &str,
+        function: &str,
+        inputs: Array,
+        imports: Option<Object>,
+    ) -> Result<Object, String> {
+        log(
+            "Disclaimer: Fee estimation is experimental and may not represent a correct estimate on any current or future network",
         );
+        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
+        let process = &mut process_native;
 
-        // Execute the program
-        let node_url = url.as_deref().unwrap_or(DEFAULT_URL);
-        let program = ProgramNative::from_str(program).map_err(|err| err.to_string())?;
-        let locator = program.id().to_string().add("/").add(function);
-        if let Some(offline_query) = offline_query {
-            trace.prepare_async(offline_query).await.map_err(|err| err.to_string())?;
-        } else {
-            let query = QueryNative::from(node_url);
-            trace.prepare_async(query).await.map_err(|err| err.to_string())?;
+        let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
+        ProgramManager::resolve_imports(process, &program_native, imports)?;
+        if !process.contains_program(program_native.id()) {
+            process.add_program(&program_native).map_err(|e| e.to_string())?;
         }
-        let execution = trace.prove_execution::<CurrentAleo, _>(&locator, rng).map_err(|e| e.to_string())?;
 
-        // Get the storage cost in bytes for the program execution
+        let inputs = ProgramManager::encode_function_inputs(&program_native, function, &inputs)?;
+        ProgramManager::check_inputs(process, &program_native, function, &inputs, private_key)?;
+
+        log("Authorizing the function to compute its transitions");
+        let function_name =
+            IdentifierNative::from_str(function).map_err(|_| "The function name provided was invalid".to_string())?;
+        let authorization = process
+            .authorize::<CurrentAleo, _>(
+                private_key,
+                program_native.id(),
+                function_name,
+                inputs.iter(),
+                &mut StdRng::from_entropy(),
+            )
+            .map_err(|err| err.to_string())?;
+        let execution = ExecutionNative::from(authorization.transitions().into_values(), Default::default(), None)
+            .map_err(|e| e.to_string())?;
+
         log("Estimating cost");
-        let storage_cost = execution.size_in_bytes().map_err(|e| e.to_string())?;
-
-        // Compute the finalize cost in microcredits.
-        let mut finalize_cost = 0u64;
-        // Iterate over the transitions to accumulate the finalize cost.
-        for transition in execution.transitions() {
-            // Retrieve the function name, program id, and program.
-            let function_name = transition.function_name();
-            let program_id = transition.program_id();
-            let stack = process.get_stack(program_id).map_err(|e| e.to_string())?;
-
-            // Calculate the finalize cost for the function identified in the transition
-            let cost = cost_in_microcredits(stack, function_name).map_err(|e| e.to_string())?;
-
-            // Accumulate the finalize cost.
-            finalize_cost = finalize_cost
-                .checked_add(cost)
-                .ok_or("The finalize cost computation overflowed for an execution".to_string())?;
+        ExecutionCostBreakdown::from_unproven_execution(process, &execution)?.to_object()
+    }
+
+    /// Itemize the cost of an execution transaction by transition, including the size of its fee
+    /// transition
+    ///
+    /// @param transaction The execution transaction
+    /// @param program The source code of the program that was executed
+    /// @param imports (optional) Provide a list of imports of the program in the form of a javascript
+    /// object where the keys are a string of the program name and the values are a string representing
+    /// the program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @returns {Object | Error} The itemized cost in the same form as `estimateExecutionCost`
+    #[wasm_bindgen(js_name = itemizeExecutionTransaction)]
+    pub fn itemize_execution_transaction(
+        transaction: &Transaction,
+        program: &str,
+        imports: Option<Object>,
+    ) -> Result<Object, String> {
+        let transaction = TransactionNative::from(transaction.clone());
+        let execution = transaction.execution().ok_or("The transaction is not an execution".to_string())?;
+
+        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
+        let process = &mut process_native;
+        let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
+        ProgramManager::resolve_imports(process, &program_native, imports)?;
+        if !process.contains_program(program_native.id()) {
+            process.add_program(&program_native).map_err(|e| e.to_string())?;
         }
-        Ok(storage_cost + finalize_cost)
+
+        ExecutionCostBreakdown::from_execution(process, execution, transaction.fee_transition().as_ref())?.to_object()
     }
 
     /// Estimate the finalize fee component for executing a function. This fee is additional to the
@@ -342,3 +509,161 @@ impl ProgramManager {
         cost_in_microcredits(stack, &function_id).map_err(|e| e.to_string())
     }
 }
+
+impl ProgramManager {
+    /// Execute and prove a function in order to measure the size of its execution. The returned
+    /// process contains the programs of the execution
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn prove_sample_execution(
+        private_key: &PrivateKey,
+        program: &str,
+        function: &str,
+        inputs: Array,
+        url: Option<String>,
+        imports: Option<Object>,
+        proving_key: Option<ProvingKey>,
+        verifying_key: Option<VerifyingKey>,
+        offline_query: Option<OfflineQuery>,
+    ) -> Result<(ProcessNative, ExecutionNative), String> {
+        log(&format!("Executing local function: {function}"));
+
+        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
+        let process = &mut process_native;
+
+        log("Check program imports are valid and add them to the process");
+        let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
+        ProgramManager::resolve_imports(process, &program_native, imports)?;
+        let rng = &mut StdRng::from_entropy();
+
+        let inputs = ProgramManager::encode_function_inputs(&program_native, function, &inputs)?;
+        ProgramManager::check_inputs(process, &program_native, function, &inputs, private_key)?;
+
+        log("Generating execution trace");
+        let (_, mut trace) =
+            execute_program!(process, inputs, program, function, private_key, proving_key, verifying_key, rng);
+
+        // Execute the program
+        let node_url = url.as_deref().unwrap_or(DEFAULT_URL);
+        let locator = program_native.id().to_string().add("/").add(function);
+        if let Some(offline_query) = offline_query {
+            trace.prepare_async(offline_query).await.map_err(|err| err.to_string())?;
+        } else {
+            let query = QueryNative::from(node_url);
+            trace.prepare_async(query).await.map_err(|err| err.to_string())?;
+        }
+        let execution = trace.prove_execution::<CurrentAleo, _>(&locator, rng).map_err(|e| e.to_string())?;
+
+        Ok((process_native, execution))
+    }
+
+    /// Execute a function in a process which already contains the imports of its program. Keys
+    /// synthesized or inserted during the execution remain in the process
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn execute_in_process(
+        process: &mut ProcessNative,
+        private_key: &PrivateKey,
+        program_native: &ProgramNative,
+        function: &str,
+        inputs: &Array,
+        prove_execution: bool,
+        proving_key: Option<ProvingKey>,
+        verifying_key: Option<VerifyingKey>,
+        url: Option<String>,
+        offline_query: Option<OfflineQuery>,
+    ) -> Result<ExecutionResponse, String> {
+        let node_url = url.as_deref().unwrap_or(DEFAULT_URL);
+        let program = &program_native.to_string();
+        let rng = &mut StdRng::from_entropy();
+
+        let inputs = ProgramManager::encode_function_inputs(program_native, function, inputs)?;
+        ProgramManager::check_inputs(process, program_native, function, &inputs, private_key)?;
+
+        let (response, mut trace) =
+            execute_program!(process, inputs, program, function, private_key, proving_key, verifying_key, rng);
+
+        if prove_execution {
+            log("Preparing inclusion proofs for execution");
+            if let Some(offline_query) = offline_query {
+                trace.prepare_async(offline_query).await.map_err(|err| err.to_string())?;
+            } else {
+                let query = QueryNative::from(node_url);
+                trace.prepare_async(query).await.map_err(|err| err.to_string())?;
+            }
+
+            log("Proving execution");
+            let locator = program_native.id().to_string().add("/").add(function);
+            let execution = trace.prove_execution::<CurrentAleo, _>(&locator, rng).map_err(|e| e.to_string())?;
+            ExecutionResponse::new(Some(execution), function, response, process, program)
+        } else {
+            ExecutionResponse::new(None, function, response, process, program)
+        }
+    }
+
+    /// Build an execution transaction in a process which already contains the imports of the
+    /// program being executed. Fees are expected to have been validated by the caller
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn build_execution_in_process(
+        process: &mut ProcessNative,
+        private_key: &PrivateKey,
+        program_native: &ProgramNative,
+        function: &str,
+        inputs: &Array,
+        fee_microcredits: u64,
+        priority_fee_microcredits: u64,
+        fee_record: Option<RecordPlaintext>,
+        url: Option<String>,
+        proving_key: Option<ProvingKey>,
+        verifying_key: Option<VerifyingKey>,
+        fee_proving_key: Option<ProvingKey>,
+        fee_verifying_key: Option<VerifyingKey>,
+        offline_query: Option<OfflineQuery>,
+    ) -> Result<Transaction, String> {
+        let node_url = url.as_deref().unwrap_or(DEFAULT_URL);
+        let program = &program_native.to_string();
+        let rng = &mut StdRng::from_entropy();
+
+        let inputs = ProgramManager::encode_function_inputs(program_native, function, inputs)?;
+        ProgramManager::check_inputs(process, program_native, function, &inputs, private_key)?;
+
+        log("Executing program");
+        let (_, mut trace) =
+            execute_program!(process, inputs, program, function, private_key, proving_key, verifying_key, rng);
+
+        log("Preparing inclusion proofs for execution");
+        if let Some(offline_query) = offline_query.as_ref() {
+            trace.prepare_async(offline_query.clone()).await.map_err(|err| err.to_string())?;
+        } else {
+            let query = QueryNative::from(node_url);
+            trace.prepare_async(query).await.map_err(|err| err.to_string())?;
+        }
+
+        log("Proving execution");
+        let locator = program_native.id().to_string().add("/").add(function);
+        let execution = trace
+            .prove_execution::<CurrentAleo, _>(&locator, &mut StdRng::from_entropy())
+            .map_err(|e| e.to_string())?;
+        let execution_id = execution.to_execution_id().map_err(|e| e.to_string())?;
+
+        log("Executing fee");
+        let fee = execute_fee!(
+            process,
+            private_key,
+            fee_record,
+            fee_microcredits,
+            priority_fee_microcredits,
+            node_url,
+            fee_proving_key,
+            fee_verifying_key,
+            execution_id,
+            rng,
+            offline_query
+        );
+
+        // Verify the execution
+        process.verify_execution(&execution).map_err(|err| err.to_string())?;
+
+        log("Creating execution transaction");
+        let transaction = TransactionNative::from_execution(execution, Some(fee)).map_err(|err| err.to_string())?;
+        Ok(Transaction::from(transaction))
+    }
+}
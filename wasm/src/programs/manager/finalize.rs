@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use crate::{log, Execution, MappingStore};
+
+use crate::types::native::{ProcessNative, ProgramNative};
+use js_sys::Object;
+use std::str::FromStr;
+
+#[wasm_bindgen]
+impl ProgramManager {
+    /// Simulate the finalize logic of an execution against an in-memory mapping store. The store
+    /// is only modified if finalize succeeds, so several executions can be simulated in sequence
+    /// to predict their combined effect on chain.
+    ///
+    /// @param {MappingStore} store The mapping store seeded with the mapping values finalize reads
+    /// @param {Execution} execution The execution to finalize
+    /// @param {string} program The source code of the program that was executed
+    /// @param {Object | undefined} imports (optional) Provide a list of imports of the program in the
+    /// form of a javascript object where the keys are a string of the program name and the values
+    /// are a string representing the program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @param {number | undefined} block_height (optional) The block height finalize is run at, defaults to 0
+    /// @returns {Object | Error} An object of the form \{ accepted: boolean, error: string | null, diffs: Array \}
+    /// where each diff is of the form \{ program: string, mapping: string, key: string, before: string | null,
+    /// after: string | null \}
+    #[wasm_bindgen(js_name = simulateFinalize)]
+    pub fn simulate_finalize(
+        store: &mut MappingStore,
+        execution: &Execution,
+        program: &str,
+        imports: Option<Object>,
+        block_height: Option<u32>,
+    ) -> Result<Object, String> {
+        log("Simulating finalize");
+        let mut process_native = ProcessNative::load_web().map_err(|err| err.to_string())?;
+        let process = &mut process_native;
+
+        let program_native = ProgramNative::from_str(program).map_err(|e| e.to_string())?;
+        ProgramManager::resolve_imports(process, &program_native, imports)?;
+        if !process.contains_program(program_native.id()) {
+            process.add_program(&program_native).map_err(|e| e.to_string())?;
+        }
+
+        store.finalize(process, execution, block_height.unwrap_or(0))?.to_object()
+    }
+}
@@ -42,18 +42,23 @@ use std::str::FromStr;
 #[wasm_bindgen]
 impl ProgramManager {
     /// Join two records together to create a new record with an amount of credits equal to the sum
-    /// of the credits of the two original records
+    /// of the credits of the two original records. Keys which are not passed are loaded from the
+    /// default key store if one is set with `KeyStore.setDefault`
     ///
     /// @param private_key The private key of the sender
     /// @param record_1 The first record to combine
     /// @param record_2 The second record to combine
-    /// @param fee_credits The amount of credits to pay as a fee
+    /// @param fee_credits The fee to pay, as a bigint of microcredits, a decimal string of credits or
+    /// (deprecated) a number of credits
     /// @param fee_record The record to spend the fee from
     /// @param url The url of the Aleo network node to send the transaction to
     /// @param join_proving_key (optional) Provide a proving key to use for the join function
     /// @param join_verifying_key (optional) Provide a verifying key to use for the join function
     /// @param fee_proving_key (optional) Provide a proving key to use for the fee execution
     /// @param fee_verifying_key (optional) Provide a verifying key to use for the fee execution
+    /// @param priority_fee_credits (optional) The priority fee to pay on top of the base fee, as a
+    /// bigint of microcredits, a decimal string of credits or (deprecated) a number of credits. If a
+    /// fee record is provided, it must hold enough credits to pay both fees
     /// @returns {Transaction | Error} Transaction object
     #[wasm_bindgen(js_name = buildJoinTransaction)]
     #[allow(clippy::too_many_arguments)]
@@ -61,7 +66,7 @@ impl ProgramManager {
         private_key: &PrivateKey,
         record_1: RecordPlaintext,
         record_2: RecordPlaintext,
-        fee_credits: f64,
+        fee_credits: JsValue,
         fee_record: Option<RecordPlaintext>,
         url: Option<String>,
         join_proving_key: Option<ProvingKey>,
@@ -69,12 +74,11 @@ impl ProgramManager {
         fee_proving_key: Option<ProvingKey>,
         fee_verifying_key: Option<VerifyingKey>,
         offline_query: Option<OfflineQuery>,
+        priority_fee_credits: JsValue,
     ) -> Result<Transaction, String> {
         log("Executing join program");
-        let fee_microcredits = match &fee_record {
-            Some(fee_record) => Self::validate_amount(fee_credits, fee_record, true)?,
-            None => (fee_credits * 1_000_000.0) as u64,
-        };
+        let (fee_microcredits, priority_fee_microcredits) =
+            Self::validate_fees(&fee_credits, &priority_fee_credits, fee_record.as_ref())?;
         let rng = &mut StdRng::from_entropy();
 
         log("Setup program and inputs");
@@ -104,6 +108,11 @@ impl ProgramManager {
                 .map_err(|e| e.to_string())?;
         }
 
+        log("Loading stored keys from the default key store");
+        let (join_locator, fee_locator) = (Self::credits_locator("join")?, Self::fee_locator(fee_record.is_some())?);
+        let passed = if join_proving_key.is_some() { vec![join_locator] } else { vec![] };
+        let missing = Self::load_default_keys(process, &[join_locator, fee_locator], &passed).await?;
+
         log("Executing the join function");
         let (_, mut trace) = execute_program!(
             process,
@@ -137,6 +146,7 @@ impl ProgramManager {
             private_key,
             fee_record,
             fee_microcredits,
+            priority_fee_microcredits,
             node_url,
             fee_proving_key,
             fee_verifying_key,
@@ -145,6 +155,8 @@ impl ProgramManager {
             offline_query
         );
 
+        Self::persist_default_keys(process, &missing).await;
+
         log("Creating execution transaction for join");
         let transaction = TransactionNative::from_execution(execution, Some(fee)).map_err(|err| err.to_string())?;
         Ok(Transaction::from(transaction))
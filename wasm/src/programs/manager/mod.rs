@@ -14,19 +14,40 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod cache;
 pub mod deploy;
 pub mod execute;
+pub mod finalize;
 pub mod join;
+pub mod plan;
 pub mod split;
 pub mod transfer;
 
 const DEFAULT_URL: &str = "https://api.explorer.aleo.org/v1";
 
-use crate::{KeyPair, PrivateKey, ProvingKey, RecordPlaintext, VerifyingKey};
+use crate::{
+    encode_inputs,
+    program_checksum,
+    to_microcredits,
+    validate_inputs,
+    Credits,
+    FallbackResolver,
+    ImportResolver,
+    KeyPair,
+    KeyStore,
+    MemoryResolver,
+    NetworkResolver,
+    OfflineQuery,
+    PrivateKey,
+    ProvingKey,
+    RecordPlaintext,
+    VerifyingKey,
+};
 
 use crate::types::native::{
     cost_in_microcredits,
     deployment_cost,
+    AddressNative,
     IdentifierNative,
     ProcessNative,
     ProgramIDNative,
@@ -35,32 +56,117 @@ use crate::types::native::{
     QueryNative,
     VerifyingKeyNative,
 };
-use js_sys::{Object, Reflect};
+use futures::FutureExt;
+use indexmap::IndexMap;
+use js_sys::{Array, Object, Reflect};
 use std::str::FromStr;
-use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
+/// The ProgramManager creates executions, deployments and transfers.
+///
+/// Its static methods create a new process for every call. A ProgramManager instance instead owns
+/// a long-lived process in which added programs and synthesized or inserted keys are cached across
+/// calls, so repeated executions of the same function skip reloading programs and re-synthesizing
+/// keys. Instances can also be given a key store, from which keys are loaded before they would be
+/// synthesized and to which newly synthesized keys are persisted.
+///
+/// Asynchronous instance methods (such as `executeFunction`) run on a clone of the program manager
+/// so the returned promise does not borrow it. Cloning the process shares its program stacks,
+/// which hold the keys, so keys synthesized by a pending call land in this instance. Programs added
+/// after the call started are not visible to it, and if `evictProgram` or `clear` replaces the
+/// process while a call is pending, the keys synthesized by that call are not kept.
 #[wasm_bindgen]
 #[derive(Clone)]
-pub struct ProgramManager;
+pub struct ProgramManager {
+    process: ProcessNative,
+    programs: IndexMap<ProgramIDNative, ProgramNative>,
+    key_sizes: IndexMap<(ProgramIDNative, IdentifierNative), (usize, usize)>,
+    key_store: Option<KeyStore>,
+}
 
 #[wasm_bindgen]
 impl ProgramManager {
     /// Validate that an amount being paid from a record is greater than zero and that the record
-    /// has enough credits to pay the amount
-    pub(crate) fn validate_amount(credits: f64, amount: &RecordPlaintext, fee: bool) -> Result<u64, String> {
+    /// has enough credits to pay the amount. Amounts are converted to exact microcredits with
+    /// `to_microcredits`
+    pub(crate) fn validate_amount(credits: &JsValue, amount: &RecordPlaintext, fee: bool) -> Result<u64, String> {
         let name = if fee { "Fee" } else { "Amount" };
 
-        if credits <= 0.0 {
+        let microcredits = to_microcredits(credits).map_err(|e| format!("{name} is invalid: {e}"))?;
+        if microcredits == 0 {
             return Err(format!("{name} must be greater than zero to deploy or execute a program"));
         }
-        let microcredits = (credits * 1_000_000.0f64) as u64;
-        if amount.microcredits() < microcredits {
-            return Err(format!("{name} record does not have enough credits to pay the specified fee"));
-        }
+        Self::validate_balance(u128::from(microcredits), amount, name)?;
 
         Ok(microcredits)
     }
 
+    /// Convert a base fee and an optional priority fee to microcredits. If the fee is paid from a
+    /// record, the record must hold enough credits to pay both fees together
+    pub(crate) fn validate_fees(
+        fee_credits: &JsValue,
+        priority_fee_credits: &JsValue,
+        fee_record: Option<&RecordPlaintext>,
+    ) -> Result<(u64, u64), String> {
+        let priority_fee_microcredits = if priority_fee_credits.is_undefined() || priority_fee_credits.is_null() {
+            0
+        } else {
+            to_microcredits(priority_fee_credits).map_err(|e| format!("Priority fee is invalid: {e}"))?
+        };
+
+        let fee_microcredits = match fee_record {
+            Some(fee_record) => {
+                let fee_microcredits = Self::validate_amount(fee_credits, fee_record, true)?;
+                let total = u128::from(fee_microcredits) + u128::from(priority_fee_microcredits);
+                Self::validate_balance(total, fee_record, "Fee")?;
+                fee_microcredits
+            }
+            None => to_microcredits(fee_credits).map_err(|e| format!("Fee is invalid: {e}"))?,
+        };
+
+        Ok((fee_microcredits, priority_fee_microcredits))
+    }
+
+    /// Validate that a credits record holds at least the amount of microcredits being paid from it
+    pub(crate) fn validate_balance(amount: u128, record: &RecordPlaintext, name: &str) -> Result<(), String> {
+        if u128::from(Credits::microcredits(&**record)?) < amount {
+            return Err(format!("{name} record does not have enough credits to pay {amount} microcredits"));
+        }
+        Ok(())
+    }
+
+    /// Convert the javascript inputs of a function call into Aleo strings using the function's
+    /// declared input types
+    pub(crate) fn encode_function_inputs(
+        program: &ProgramNative,
+        function: &str,
+        inputs: &Array,
+    ) -> Result<Vec<String>, String> {
+        let function_id =
+            IdentifierNative::from_str(function).map_err(|_| "The function name provided was invalid".to_string())?;
+        encode_inputs(program, &function_id, inputs)
+    }
+
+    /// Validate the inputs of a function call against the function's declared input types before
+    /// any keys are synthesized. Every invalid input is reported in the returned error
+    pub(crate) fn check_inputs(
+        process: &ProcessNative,
+        program: &ProgramNative,
+        function: &str,
+        inputs: &[String],
+        private_key: &PrivateKey,
+    ) -> Result<(), String> {
+        let function_id =
+            IdentifierNative::from_str(function).map_err(|_| "The function name provided was invalid".to_string())?;
+        let caller = AddressNative::try_from(**private_key).map_err(|e| e.to_string())?;
+        let diagnostics = validate_inputs(process, program, &function_id, inputs, Some(&caller))?;
+        if diagnostics.is_empty() {
+            return Ok(());
+        }
+        let diagnostics = diagnostics.iter().map(|diagnostic| diagnostic.to_string()).collect::<Vec<_>>();
+        Err(format!("Invalid inputs for {}/{function}: {}", program.id(), diagnostics.join("; ")))
+    }
+
     /// Synthesize proving and verifying keys for a program
     ///
     /// @param program {string} The program source code of the program to synthesize keys for
@@ -104,36 +210,119 @@ impl ProgramManager {
         )
     }
 
-    /// Resolve imports for a program in depth first search order
+    /// Resolve imports for a program in dependency order and add those which are not already part
+    /// of the process. Every such import must be present in the imports object and declare the
+    /// program id it is listed under
     pub(crate) fn resolve_imports(
         process: &mut ProcessNative,
         program: &ProgramNative,
         imports: Option<Object>,
     ) -> Result<(), String> {
-        if let Some(imports) = imports {
-            program.imports().keys().try_for_each(|program_id| {
-                // Get the program string
-                let program_id = program_id.to_string();
-                if let Some(import_string) = Reflect::get(&imports, &program_id.as_str().into())
-                    .map_err(|_| "Program import not found in imports provided".to_string())?
-                    .as_string()
-                {
-                    if &program_id != "credits.aleo" {
-                        crate::log(&format!("Importing program: {}", program_id));
-                        let import = ProgramNative::from_str(&import_string).map_err(|err| err.to_string())?;
-                        // If the program has imports, add them
-                        Self::resolve_imports(process, &import, Some(imports.clone()))?;
-                        // If the process does not already contain the program, add it
-                        if !process.contains_program(import.id()) {
-                            process.add_program(&import).map_err(|err| err.to_string())?;
-                        }
-                    }
+        Self::resolve_pinned_imports(process, program, imports, None)
+    }
+
+    /// Resolve imports for a program like `resolve_imports`, rejecting any import whose checksum
+    /// differs from the checksum it is pinned to before it is added to the process
+    pub(crate) fn resolve_pinned_imports(
+        process: &mut ProcessNative,
+        program: &ProgramNative,
+        imports: Option<Object>,
+        checksums: Option<Object>,
+    ) -> Result<(), String> {
+        let imports = match imports {
+            Some(imports) => MemoryResolver::from_object(&imports)?,
+            None => MemoryResolver::new(),
+        };
+        let mut resolver = ImportResolver::new(imports);
+        if let Some(checksums) = checksums {
+            resolver.pin_object(&checksums)?;
+        }
+        // Programs are resolved from memory, so the resolution completes without suspending.
+        resolver
+            .add_imports(process, program)
+            .now_or_never()
+            .unwrap_or_else(|| Err("Failed to resolve the imports of the program".to_string()))
+    }
+
+    /// Resolve all imports of a program, including nested imports. Imports are looked up in the
+    /// imports provided first and fetched from the network otherwise
+    ///
+    /// @param {string} program The source code of the program to resolve the imports of
+    /// @param {Object | undefined} imports (optional) Known imports in the form of a javascript object
+    /// where the keys are a string of the program name and the values are a string representing the
+    /// program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @param {string | undefined} url (optional) The url of the Aleo network node (or mock server) to
+    /// fetch missing imports from
+    /// @returns {Object | Error} An object of the same form as the imports parameter containing every
+    /// import of the program, which can be passed to the other ProgramManager methods
+    #[wasm_bindgen(js_name = resolveImports)]
+    pub async fn resolve_all_imports(
+        program: &str,
+        imports: Option<Object>,
+        url: Option<String>,
+    ) -> Result<Object, String> {
+        let program = ProgramNative::from_str(program).map_err(|err| err.to_string())?;
+        let known = match imports {
+            Some(imports) => MemoryResolver::from_object(&imports)?,
+            None => MemoryResolver::new(),
+        };
+        let resolver = ImportResolver::new(FallbackResolver::new(
+            known,
+            NetworkResolver::new(url.as_deref().unwrap_or(DEFAULT_URL)),
+        ));
+
+        let resolved = Object::new();
+        for import in resolver.resolve_imports(&program, |_| false).await? {
+            Reflect::set(&resolved, &import.id().to_string().into(), &import.to_string().into())
+                .map_err(|_| "Failed to set the resolved import".to_string())?;
+        }
+        Ok(resolved)
+    }
+
+    /// Check that the imports of a program match the programs they are pinned to before using them
+    /// to build a transaction. Every import is pinned either to the checksum provided for it or,
+    /// if none is provided, to the checksum of the program deployed on chain
+    ///
+    /// @param {string} program The source code of the program whose imports are checked
+    /// @param {Object} imports The imports of the program in the form of a javascript object where
+    /// the keys are a string of the program name and the values are a string representing the
+    /// program source code \{ "hello.aleo": "hello.aleo source code" \}
+    /// @param {Object | undefined} checksums (optional) The checksums to pin imports to, as returned
+    /// by `Program.checksum`, in the form \{ "hello.aleo": "checksum" \}
+    /// @param {string | undefined} url (optional) The url of the Aleo network node to query the
+    /// checksums of imports without a pinned checksum from
+    /// @param {OfflineQuery | undefined} offline_query (optional) An offline query holding the
+    /// checksums of the deployed imports, used instead of the node
+    /// @returns {Object | Error} The checksums of every import in the form \{ "hello.aleo": "checksum" \}
+    #[wasm_bindgen(js_name = verifyImports)]
+    pub async fn verify_imports(
+        program: &str,
+        imports: Object,
+        checksums: Option<Object>,
+        url: Option<String>,
+        offline_query: Option<OfflineQuery>,
+    ) -> Result<Object, String> {
+        let program = ProgramNative::from_str(program).map_err(|err| err.to_string())?;
+        let mut resolver = ImportResolver::new(MemoryResolver::from_object(&imports)?);
+        if let Some(checksums) = checksums {
+            resolver.pin_object(&checksums)?;
+        }
+
+        let query = QueryNative::from(url.as_deref().unwrap_or(DEFAULT_URL));
+        let verified = Object::new();
+        let imports = resolver.resolve_imports(&program, |_| false).await?;
+        for import in imports {
+            if !resolver.is_pinned(import.id()) {
+                match &offline_query {
+                    Some(offline_query) => resolver.pin_onchain(*import.id(), offline_query).await?,
+                    None => resolver.pin_onchain(*import.id(), &query).await?,
                 }
-                Ok::<(), String>(())
-            })
-        } else {
-            Ok(())
+            }
+            resolver.check_pin(&import)?;
+            Reflect::set(&verified, &import.id().to_string().into(), &program_checksum(&import)?.into())
+                .map_err(|_| "Failed to set the import checksum".to_string())?;
         }
+        Ok(verified)
     }
 }
 
@@ -217,4 +406,17 @@ function add_and_double:
         assert_eq!(double_import, &double_program);
         assert!(main_program.is_err());
     }
+
+    #[wasm_bindgen_test]
+    fn test_missing_import_resolution() {
+        let imports = Object::new();
+        Reflect::set(&imports, &JsValue::from_str("addition_test.aleo"), &JsValue::from_str(ADDITION_PROGRAM)).unwrap();
+        Reflect::set(&imports, &JsValue::from_str("double_test.aleo"), &JsValue::from_str(MULTIPLY_IMPORT_PROGRAM))
+            .unwrap();
+
+        let mut process = ProcessNative::load_web().unwrap();
+        let program = ProgramNative::from_str(NESTED_IMPORT_PROGRAM).unwrap();
+        let error = ProgramManager::resolve_imports(&mut process, &program, Some(imports)).unwrap_err();
+        assert!(error.contains("multiply_test.aleo was not found"));
+    }
 }
@@ -0,0 +1,200 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use crate::published_function;
+
+use crate::types::native::{CallOperator, IdentifierNative, Instruction, ProcessNative, ProgramIDNative};
+use js_sys::{Array, Object, Promise, Reflect};
+use std::str::FromStr;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::future_to_promise;
+
+/// A proving and verifying key needed to execute a function, along with where it is available
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyRequirement {
+    pub program_id: ProgramIDNative,
+    pub function_id: IdentifierNative,
+    /// The size of the serialized proving key, if it is known before the key is loaded
+    pub proving_key_bytes: Option<u64>,
+    /// The size of the serialized verifying key, if it is known before the key is loaded
+    pub verifying_key_bytes: Option<u64>,
+    /// Whether the keys are cached in the program manager's process
+    pub cached: bool,
+    /// Whether the keys are persisted in the program manager's key store
+    pub stored: bool,
+    /// Whether the keys are published on the parameter server
+    pub published: bool,
+}
+
+impl KeyRequirement {
+    fn to_object(&self) -> Result<Object, String> {
+        let size = |bytes: Option<u64>| bytes.map_or(JsValue::UNDEFINED, |bytes| JsValue::from(bytes as f64));
+        let requirement = Object::new();
+        Reflect::set(&requirement, &"programId".into(), &JsValue::from_str(&self.program_id.to_string()))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&requirement, &"function".into(), &JsValue::from_str(&self.function_id.to_string()))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&requirement, &"provingKeyBytes".into(), &size(self.proving_key_bytes))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&requirement, &"verifyingKeyBytes".into(), &size(self.verifying_key_bytes))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&requirement, &"cached".into(), &JsValue::from_bool(self.cached))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&requirement, &"stored".into(), &JsValue::from_bool(self.stored))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&requirement, &"published".into(), &JsValue::from_bool(self.published))
+            .map_err(|_| "Failed to set property")?;
+        Ok(requirement)
+    }
+}
+
+#[wasm_bindgen]
+impl ProgramManager {
+    /// List the keys needed to execute a function of a cached program, including the functions it
+    /// calls in imported programs and the fee function, so they can be prefetched before proving
+    ///
+    /// @param {string} program_id The id of the cached program
+    /// @param {string} function The name of the function to execute
+    /// @param {string | undefined} fee (optional) "private" if the fee is paid from a record,
+    /// "public" if it is paid from the public balance, or undefined if no fee is paid
+    /// @returns {Promise<Array>} The keys needed in execution order, each of the form \{ programId: string,
+    /// function: string, provingKeyBytes: number | undefined, verifyingKeyBytes: number | undefined,
+    /// cached: boolean, stored: boolean, published: boolean \}. Sizes are known for cached keys and
+    /// for published credits.aleo keys
+    #[wasm_bindgen(js_name = planKeys)]
+    pub fn plan_keys_js(&self, program_id: &str, function: &str, fee: Option<String>) -> Promise {
+        let mut manager = self.clone();
+        let (program_id, function) = (program_id.to_string(), function.to_string());
+        future_to_promise(async move {
+            let requirements = Array::new();
+            for requirement in manager.plan_keys(&program_id, &function, fee.as_deref()).await? {
+                requirements.push(&requirement.to_object()?);
+            }
+            Ok(JsValue::from(requirements))
+        })
+    }
+}
+
+impl ProgramManager {
+    /// List the keys needed to execute a function of a cached program and pay the given fee type
+    pub async fn plan_keys(
+        &mut self,
+        program_id: &str,
+        function: &str,
+        fee: Option<&str>,
+    ) -> Result<Vec<KeyRequirement>, String> {
+        let (program_id, function_id) = self.function_locator(program_id, function)?;
+        let mut requirements = vec![];
+        for (program_id, function_id) in Self::required_functions(&self.process, program_id, function_id, fee)? {
+            let cached = Self::contains_key(&self.process, &program_id, &function_id);
+            let stored = match &self.key_store {
+                Some(key_store) => {
+                    let program = self.process.get_program(program_id).map_err(|err| err.to_string())?;
+                    key_store.contains_keys(program, &function_id).await?
+                }
+                None => false,
+            };
+            let published = if program_id.to_string() == "credits.aleo" {
+                published_function(&function_id.to_string()).ok()
+            } else {
+                None
+            };
+
+            let (proving_key_bytes, verifying_key_bytes) = if cached {
+                let (proving, verifying) = self.cached_key_sizes(program_id, function_id)?;
+                (Some(proving as u64), Some(verifying as u64))
+            } else if let Some(published) = &published {
+                let verifying_key = (published.verifier)().to_bytes()?;
+                (published.metadata.prover_size, Some(verifying_key.len() as u64))
+            } else {
+                (None, None)
+            };
+
+            requirements.push(KeyRequirement {
+                program_id,
+                function_id,
+                proving_key_bytes,
+                verifying_key_bytes,
+                cached,
+                stored,
+                published: published.is_some(),
+            });
+        }
+        Ok(requirements)
+    }
+
+    /// Get the functions whose keys are needed to execute a function, starting with the function
+    /// itself, followed by every function it calls directly or through imported programs and
+    /// finally by the fee function if a "private" or "public" fee is paid
+    pub(crate) fn required_functions(
+        process: &ProcessNative,
+        program_id: ProgramIDNative,
+        function_id: IdentifierNative,
+        fee: Option<&str>,
+    ) -> Result<Vec<(ProgramIDNative, IdentifierNative)>, String> {
+        let mut required = vec![(program_id, function_id)];
+        let mut index = 0;
+        while let Some((program_id, function_id)) = required.get(index).copied() {
+            let program = process.get_program(program_id).map_err(|err| err.to_string())?;
+            let function = program
+                .functions()
+                .get(&function_id)
+                .ok_or_else(|| format!("Program {program_id} does not contain the function {function_id}"))?;
+            for instruction in function.instructions() {
+                if let Instruction::Call(call) = instruction {
+                    let callee = match call.operator() {
+                        CallOperator::Locator(locator) => (*locator.program_id(), *locator.resource()),
+                        CallOperator::Resource(resource) => (program_id, *resource),
+                    };
+                    // Calls to closures are proven as part of the calling function
+                    let callee_program = process.get_program(callee.0).map_err(|err| err.to_string())?;
+                    if callee_program.contains_function(&callee.1) && !required.contains(&callee) {
+                        required.push(callee);
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        let fee_locator = match fee {
+            Some("private") => Some(Self::fee_locator(true)?),
+            Some("public") => Some(Self::fee_locator(false)?),
+            Some(fee) => return Err(format!("Unknown fee type {fee}, expected \"private\" or \"public\"")),
+            None => None,
+        };
+        if let Some(fee_locator) = fee_locator {
+            if !required.contains(&fee_locator) {
+                required.push(fee_locator);
+            }
+        }
+        Ok(required)
+    }
+
+    /// Get the credits.aleo function paying a fee from a record or from the public balance
+    pub(crate) fn fee_locator(private: bool) -> Result<(ProgramIDNative, IdentifierNative), String> {
+        Self::credits_locator(if private { "fee_private" } else { "fee_public" })
+    }
+
+    /// Get the locator of a credits.aleo function
+    pub(crate) fn credits_locator(function: &str) -> Result<(ProgramIDNative, IdentifierNative), String> {
+        Ok((
+            ProgramIDNative::from_str("credits.aleo").map_err(|err| err.to_string())?,
+            IdentifierNative::from_str(function).map_err(|err| err.to_string())?,
+        ))
+    }
+}
@@ -26,10 +26,14 @@ use std::{ops::Add, str::FromStr};
 #[wasm_bindgen]
 impl ProgramManager {
     /// Split an Aleo credits record into two separate records. This function does not require a fee.
+    /// Keys which are not passed are loaded from the default key store if one is set with
+    /// `KeyStore.setDefault`
     ///
     /// @param private_key The private key of the sender
     /// @param split_amount The amount of the credit split. This amount will be subtracted from the
-    /// value of the record and two new records will be created with the split amount and the remainder
+    /// value of the record and two new records will be created with the split amount and the remainder.
+    /// The amount can be a bigint of microcredits, a decimal string of credits or (deprecated) a number
+    /// of credits
     /// @param amount_record The record to split
     /// @param url The url of the Aleo network node to send the transaction to
     /// @param split_proving_key (optional) Provide a proving key to use for the split function
@@ -39,7 +43,7 @@ impl ProgramManager {
     #[allow(clippy::too_many_arguments)]
     pub async fn split(
         private_key: &PrivateKey,
-        split_amount: f64,
+        split_amount: JsValue,
         amount_record: RecordPlaintext,
         url: Option<String>,
         split_proving_key: Option<ProvingKey>,
@@ -47,7 +51,7 @@ impl ProgramManager {
         offline_query: Option<OfflineQuery>,
     ) -> Result<Transaction, String> {
         log("Executing split program");
-        let amount_microcredits = Self::validate_amount(split_amount, &amount_record, false)?;
+        let amount_microcredits = Self::validate_amount(&split_amount, &amount_record, false)?;
 
         log("Setup the program and inputs");
         let node_url = url.as_deref().unwrap_or(DEFAULT_URL);
@@ -60,6 +64,11 @@ impl ProgramManager {
         let process = &mut process_native;
         let rng = &mut StdRng::from_entropy();
 
+        log("Loading stored keys from the default key store");
+        let split_locator = Self::credits_locator("split")?;
+        let passed = if split_proving_key.is_some() { vec![split_locator] } else { vec![] };
+        let missing = Self::load_default_keys(process, &[split_locator], &passed).await?;
+
         log("Executing the split function");
         let (_, mut trace) = execute_program!(
             process,
@@ -87,6 +96,8 @@ impl ProgramManager {
         log("Verifying the split execution");
         process.verify_execution(&execution).map_err(|err| err.to_string())?;
 
+        Self::persist_default_keys(process, &missing).await;
+
         log("Creating execution transaction for split");
         let transaction = TransactionNative::from_execution(execution, None).map_err(|err| err.to_string())?;
         Ok(Transaction::from(transaction))
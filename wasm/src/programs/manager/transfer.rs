@@ -41,30 +41,36 @@ use wasm_bindgen::JsValue;
 
 #[wasm_bindgen]
 impl ProgramManager {
-    /// Send credits from one Aleo account to another
+    /// Send credits from one Aleo account to another. Keys which are not passed are loaded from the
+    /// default key store if one is set with `KeyStore.setDefault`
     ///
     /// @param private_key The private key of the sender
-    /// @param amount_credits The amount of credits to send
+    /// @param amount_credits The amount to send, as a bigint of microcredits, a decimal string of
+    /// credits or (deprecated) a number of credits
     /// @param recipient The recipient of the transaction
     /// @param transfer_type The type of the transfer (options: "private", "public", "private_to_public", "public_to_private")
     /// @param amount_record The record to fund the amount from
-    /// @param fee_credits The amount of credits to pay as a fee
+    /// @param fee_credits The fee to pay, as a bigint of microcredits, a decimal string of credits or
+    /// (deprecated) a number of credits
     /// @param fee_record The record to spend the fee from
     /// @param url The url of the Aleo network node to send the transaction to
     /// @param transfer_verifying_key (optional) Provide a verifying key to use for the transfer
     /// function
     /// @param fee_proving_key (optional) Provide a proving key to use for the fee execution
     /// @param fee_verifying_key (optional) Provide a verifying key to use for the fee execution
+    /// @param priority_fee_credits (optional) The priority fee to pay on top of the base fee, as a
+    /// bigint of microcredits, a decimal string of credits or (deprecated) a number of credits. If a
+    /// fee record is provided, it must hold enough credits to pay both fees
     /// @returns {Transaction}
     #[wasm_bindgen(js_name = buildTransferTransaction)]
     #[allow(clippy::too_many_arguments)]
     pub async fn transfer(
         private_key: &PrivateKey,
-        amount_credits: f64,
+        amount_credits: JsValue,
         recipient: &str,
         transfer_type: &str,
         amount_record: Option<RecordPlaintext>,
-        fee_credits: f64,
+        fee_credits: JsValue,
         fee_record: Option<RecordPlaintext>,
         url: Option<String>,
         transfer_proving_key: Option<ProvingKey>,
@@ -72,15 +78,14 @@ impl ProgramManager {
         fee_proving_key: Option<ProvingKey>,
         fee_verifying_key: Option<VerifyingKey>,
         offline_query: Option<OfflineQuery>,
+        priority_fee_credits: JsValue,
     ) -> Result<Transaction, String> {
         log("Executing transfer program");
-        let fee_microcredits = match &fee_record {
-            Some(fee_record) => Self::validate_amount(fee_credits, fee_record, true)?,
-            None => (fee_credits * 1_000_000.0) as u64,
-        };
+        let (fee_microcredits, priority_fee_microcredits) =
+            Self::validate_fees(&fee_credits, &priority_fee_credits, fee_record.as_ref())?;
         let amount_microcredits = match &amount_record {
-            Some(amount_record) => Self::validate_amount(amount_credits, amount_record, true)?,
-            None => (amount_credits * 1_000_000.0) as u64,
+            Some(amount_record) => Self::validate_amount(&amount_credits, amount_record, false)?,
+            None => to_microcredits(&amount_credits).map_err(|e| format!("Amount is invalid: {e}"))?,
         };
 
         log("Setup the program and inputs");
@@ -158,6 +163,12 @@ impl ProgramManager {
                 .map_err(|e| e.to_string())?;
         }
 
+        log("Loading stored keys from the default key store");
+        let (transfer_locator, fee_locator) =
+            (Self::credits_locator(transfer_type)?, Self::fee_locator(fee_record.is_some())?);
+        let passed = if transfer_proving_key.is_some() { vec![transfer_locator] } else { vec![] };
+        let missing = Self::load_default_keys(process, &[transfer_locator, fee_locator], &passed).await?;
+
         log("Executing transfer function");
         let (_, mut trace) = execute_program!(
             process,
@@ -192,6 +203,7 @@ impl ProgramManager {
             private_key,
             fee_record,
             fee_microcredits,
+            priority_fee_microcredits,
             node_url,
             fee_proving_key,
             fee_verifying_key,
@@ -200,6 +212,8 @@ impl ProgramManager {
             offline_query
         );
 
+        Self::persist_default_keys(process, &missing).await;
+
         log("Creating execution transaction for transfer");
         let transaction = TransactionNative::from_execution(execution, Some(fee)).map_err(|err| err.to_string())?;
         Ok(Transaction::from(transaction))
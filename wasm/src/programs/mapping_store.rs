@@ -0,0 +1,302 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::types::native::{
+    CurrentNetwork,
+    ExecutionNative,
+    FinalizeGlobalState,
+    FinalizeStoreNative,
+    IdentifierNative,
+    PlaintextNative,
+    ProcessNative,
+    ProgramIDNative,
+    ProgramNative,
+    ValueNative,
+};
+
+use indexmap::{IndexMap, IndexSet};
+use js_sys::{Array, Object, Reflect};
+use std::str::FromStr;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+/// An in-memory store of program mappings. Mapping values can be seeded with the values of a
+/// network and the finalize logic of executions can be run against the store to predict their
+/// effect on chain before broadcasting them.
+#[wasm_bindgen]
+pub struct MappingStore(FinalizeStoreNative);
+
+#[wasm_bindgen]
+impl MappingStore {
+    /// Create an empty mapping store
+    ///
+    /// @returns {MappingStore | Error} An empty mapping store
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Result<MappingStore, String> {
+        Ok(Self(FinalizeStoreNative::open(None::<u16>).map_err(|e| e.to_string())?))
+    }
+
+    /// Set the value of a key in a mapping, initializing the mapping if it does not exist
+    ///
+    /// @param {string} program_id The id of the program the mapping belongs to
+    /// @param {string} mapping_name The name of the mapping
+    /// @param {string} key The key as an Aleo plaintext string
+    /// @param {string} value The value as an Aleo plaintext string
+    #[wasm_bindgen(js_name = "setValue")]
+    pub fn set_value(&mut self, program_id: &str, mapping_name: &str, key: &str, value: &str) -> Result<(), String> {
+        let (program_id, mapping_name) = Self::parse_mapping(program_id, mapping_name)?;
+        let key = PlaintextNative::from_str(key).map_err(|_| format!("Invalid mapping key: {key}"))?;
+        let value = ValueNative::from_str(value).map_err(|_| format!("Invalid mapping value: {value}"))?;
+        self.initialize_mapping(program_id, mapping_name)?;
+        self.0.update_key_value(program_id, mapping_name, key, value).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Get the value of a key in a mapping
+    ///
+    /// @param {string} program_id The id of the program the mapping belongs to
+    /// @param {string} mapping_name The name of the mapping
+    /// @param {string} key The key as an Aleo plaintext string
+    /// @returns {string | undefined} The value of the key if it is present in the mapping
+    #[wasm_bindgen(js_name = "getValue")]
+    pub fn get_value(&self, program_id: &str, mapping_name: &str, key: &str) -> Result<Option<String>, String> {
+        let (program_id, mapping_name) = Self::parse_mapping(program_id, mapping_name)?;
+        let key = PlaintextNative::from_str(key).map_err(|_| format!("Invalid mapping key: {key}"))?;
+        if !self.0.contains_mapping_speculative(&program_id, &mapping_name).map_err(|e| e.to_string())? {
+            return Ok(None);
+        }
+        let value = self.0.get_value_speculative(program_id, mapping_name, &key).map_err(|e| e.to_string())?;
+        Ok(value.map(|value| value.to_string()))
+    }
+
+    /// Get all the entries of a mapping
+    ///
+    /// @param {string} program_id The id of the program the mapping belongs to
+    /// @param {string} mapping_name The name of the mapping
+    /// @returns {Object | Error} An object mapping the keys of the mapping to their values
+    #[wasm_bindgen(js_name = "getMapping")]
+    pub fn get_mapping(&self, program_id: &str, mapping_name: &str) -> Result<Object, String> {
+        let (program_id, mapping_name) = Self::parse_mapping(program_id, mapping_name)?;
+        let mapping = Object::new();
+        if !self.0.contains_mapping_speculative(&program_id, &mapping_name).map_err(|e| e.to_string())? {
+            return Ok(mapping);
+        }
+        for (key, value) in self.0.get_mapping_speculative(program_id, mapping_name).map_err(|e| e.to_string())? {
+            Reflect::set(&mapping, &key.to_string().into(), &value.to_string().into())
+                .map_err(|_| "Failed to set property")?;
+        }
+        Ok(mapping)
+    }
+}
+
+impl MappingStore {
+    /// Run the finalize logic of the futures of an execution against the store. The programs of
+    /// the execution and their imports must be loaded in the process. The store is only modified
+    /// if finalize succeeds
+    pub fn finalize(
+        &mut self,
+        process: &ProcessNative,
+        execution: &ExecutionNative,
+        block_height: u32,
+    ) -> Result<FinalizeOutcome, String> {
+        // Collect the programs whose mappings finalize may read or write.
+        let mut program_ids = IndexSet::new();
+        for transition in execution.transitions() {
+            Self::collect_programs(process, transition.program_id(), &mut program_ids)?;
+        }
+        let mut programs = Vec::with_capacity(program_ids.len());
+        for program_id in program_ids.iter() {
+            let program = process.get_program(program_id).map_err(|e| e.to_string())?;
+            for mapping_name in program.mappings().keys() {
+                self.initialize_mapping(*program_id, *mapping_name)?;
+            }
+            programs.push(program.clone());
+        }
+
+        let state = FinalizeGlobalState::new::<CurrentNetwork>(0, block_height, 0, 0, Default::default())
+            .map_err(|e| e.to_string())?;
+        let (outcome, _) =
+            self.finalize_with(&programs, |store| process.finalize_execution(state, store, execution, None))?;
+        Ok(outcome)
+    }
+
+    /// Run a finalize operation against the underlying finalize store and compute the changes it
+    /// made to the mappings of the given programs. The result of the operation is returned if it
+    /// succeeded
+    pub(crate) fn finalize_with<T>(
+        &mut self,
+        programs: &[ProgramNative],
+        finalize: impl FnOnce(&FinalizeStoreNative) -> anyhow::Result<T>,
+    ) -> Result<(FinalizeOutcome, Option<T>), String> {
+        let before = self.snapshot(programs)?;
+        let result = match finalize(&self.0) {
+            Ok(result) => result,
+            Err(error) => return Ok((FinalizeOutcome { error: Some(error.to_string()), diffs: Vec::new() }, None)),
+        };
+        let after = self.snapshot(programs)?;
+
+        // Compare the mappings before and after finalize.
+        let mut diffs = Vec::new();
+        for (entry, value) in after.iter() {
+            if before.get(entry) != Some(value) {
+                diffs.push(MappingDiff::new(entry, before.get(entry).cloned(), Some(value.clone())));
+            }
+        }
+        for (entry, value) in before.iter() {
+            if !after.contains_key(entry) {
+                diffs.push(MappingDiff::new(entry, Some(value.clone()), None));
+            }
+        }
+        Ok((FinalizeOutcome { error: None, diffs }, Some(result)))
+    }
+
+    // Parse a program id and a mapping name.
+    fn parse_mapping(program_id: &str, mapping_name: &str) -> Result<(ProgramIDNative, IdentifierNative), String> {
+        let program_id =
+            ProgramIDNative::from_str(program_id).map_err(|_| format!("Invalid program id: {program_id}"))?;
+        let mapping_name =
+            IdentifierNative::from_str(mapping_name).map_err(|_| format!("Invalid mapping name: {mapping_name}"))?;
+        Ok((program_id, mapping_name))
+    }
+
+    /// Initialize a mapping if it does not exist yet
+    pub(crate) fn initialize_mapping(
+        &mut self,
+        program_id: ProgramIDNative,
+        mapping_name: IdentifierNative,
+    ) -> Result<(), String> {
+        if !self.0.contains_mapping_speculative(&program_id, &mapping_name).map_err(|e| e.to_string())? {
+            self.0.initialize_mapping(program_id, mapping_name).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Collect a program and its imports in depth first order
+    pub(crate) fn collect_programs(
+        process: &ProcessNative,
+        program_id: &ProgramIDNative,
+        program_ids: &mut IndexSet<ProgramIDNative>,
+    ) -> Result<(), String> {
+        if program_ids.insert(*program_id) {
+            let program = process.get_program(program_id).map_err(|e| e.to_string())?;
+            for import in program.imports().keys() {
+                Self::collect_programs(process, import, program_ids)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Read every entry of the initialized mappings of the given programs.
+    fn snapshot(&self, programs: &[ProgramNative]) -> Result<IndexMap<(String, String, String), String>, String> {
+        let mut entries = IndexMap::new();
+        for program in programs.iter() {
+            let program_id = program.id();
+            for mapping_name in program.mappings().keys() {
+                if !self.0.contains_mapping_speculative(program_id, mapping_name).map_err(|e| e.to_string())? {
+                    continue;
+                }
+                for (key, value) in
+                    self.0.get_mapping_speculative(*program_id, *mapping_name).map_err(|e| e.to_string())?
+                {
+                    entries
+                        .insert((program_id.to_string(), mapping_name.to_string(), key.to_string()), value.to_string());
+                }
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// A change made to a mapping entry by finalize
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MappingDiff {
+    pub program: String,
+    pub mapping: String,
+    pub key: String,
+    /// The value before finalize, `None` if the key was inserted
+    pub before: Option<String>,
+    /// The value after finalize, `None` if the key was removed
+    pub after: Option<String>,
+}
+
+impl MappingDiff {
+    fn new(entry: &(String, String, String), before: Option<String>, after: Option<String>) -> Self {
+        Self { program: entry.0.clone(), mapping: entry.1.clone(), key: entry.2.clone(), before, after }
+    }
+
+    /// Convert the diff into a javascript object
+    pub fn to_object(&self) -> Result<Object, String> {
+        let object = Object::new();
+        let optional = |value: &Option<String>| value.as_ref().map_or(JsValue::NULL, |value| value.into());
+        Reflect::set(&object, &"program".into(), &self.program.clone().into()).map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"mapping".into(), &self.mapping.clone().into()).map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"key".into(), &self.key.clone().into()).map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"before".into(), &optional(&self.before)).map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"after".into(), &optional(&self.after)).map_err(|_| "Failed to set property")?;
+        Ok(object)
+    }
+}
+
+/// The outcome of running finalize against a mapping store
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalizeOutcome {
+    /// The reason finalize failed, `None` if it succeeded
+    pub error: Option<String>,
+    /// The changes made to the mappings, empty if finalize failed
+    pub diffs: Vec<MappingDiff>,
+}
+
+impl FinalizeOutcome {
+    /// Convert the outcome into a javascript object of the form
+    /// `{ accepted: boolean, error: string | null, diffs: Array }`
+    pub fn to_object(&self) -> Result<Object, String> {
+        let diffs = Array::new_with_length(self.diffs.len() as u32);
+        for (index, diff) in self.diffs.iter().enumerate() {
+            diffs.set(index as u32, diff.to_object()?.into());
+        }
+        let object = Object::new();
+        let error = self.error.as_ref().map_or(JsValue::NULL, |error| error.into());
+        Reflect::set(&object, &"accepted".into(), &JsValue::from_bool(self.error.is_none()))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"error".into(), &error).map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"diffs".into(), &diffs).map_err(|_| "Failed to set property")?;
+        Ok(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wasm_bindgen_test::*;
+
+    const ADDRESS: &str = "aleo1cfr9mr3zum3h8tadfzan6dvvjh3cj3s0v8ha22zp88cpfn0n3gxq4m0dd0";
+
+    #[wasm_bindgen_test]
+    fn test_mapping_store() {
+        let mut store = MappingStore::new().unwrap();
+        assert_eq!(store.get_value("credits.aleo", "account", ADDRESS).unwrap(), None);
+
+        store.set_value("credits.aleo", "account", ADDRESS, "100u64").unwrap();
+        assert_eq!(store.get_value("credits.aleo", "account", ADDRESS).unwrap(), Some("100u64".to_string()));
+        store.set_value("credits.aleo", "account", ADDRESS, "50u64").unwrap();
+        assert_eq!(store.get_value("credits.aleo", "account", ADDRESS).unwrap(), Some("50u64".to_string()));
+
+        let mapping = store.get_mapping("credits.aleo", "account").unwrap();
+        assert_eq!(Reflect::get(&mapping, &ADDRESS.into()).unwrap().as_string(), Some("50u64".to_string()));
+
+        assert!(store.set_value("credits.aleo", "account", "not a key", "1u64").is_err());
+    }
+}
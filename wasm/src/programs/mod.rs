@@ -16,9 +16,36 @@
 
 mod macros;
 
+pub mod abi;
+pub use abi::*;
+
+pub mod amount;
+pub use amount::*;
+
+pub mod cost;
+pub use cost::*;
+
+pub mod credits_keys;
+pub use credits_keys::*;
+
+pub mod decoding;
+pub use decoding::*;
+
+pub mod devnet;
+pub use devnet::*;
+
+pub mod encoding;
+pub use encoding::*;
+
 pub mod execution;
 pub use execution::*;
 
+pub mod key_bundle;
+pub use key_bundle::*;
+
+pub mod key_store;
+pub use key_store::*;
+
 pub mod keypair;
 pub use keypair::*;
 
@@ -27,20 +54,35 @@ pub mod manager;
 #[cfg(feature = "browser")]
 pub use manager::*;
 
+pub mod mapping_store;
+pub use mapping_store::*;
+
 pub mod offline_query;
 pub use offline_query::*;
 
 pub mod program;
 pub use program::*;
 
+pub mod program_keys;
+pub use program_keys::*;
+
 pub mod proving_key;
 pub use proving_key::*;
 
+pub mod resolver;
+pub use resolver::*;
+
 pub mod response;
 pub use response::*;
 
 pub mod transaction;
 pub use transaction::*;
 
+pub mod typegen;
+pub use typegen::*;
+
+pub mod validation;
+pub use validation::*;
+
 pub mod verifying_key;
 pub use verifying_key::*;
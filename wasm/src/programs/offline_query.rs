@@ -14,8 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::types::native::{CurrentNetwork, Field, Network};
-use snarkvm_console::program::StatePath;
+use crate::{
+    types::native::{CurrentNetwork, Field, Network, ProgramIDNative},
+    DeployedProgramQuery,
+};
+use snarkvm_console::program::{ProgramID, StatePath};
 use snarkvm_ledger_query::QueryTrait;
 
 use anyhow::anyhow;
@@ -27,12 +30,15 @@ use wasm_bindgen::prelude::wasm_bindgen;
 use std::str::FromStr;
 
 /// An offline query object used to insert the global state root and state paths needed to create
-/// a valid inclusion proof offline.
+/// a valid inclusion proof offline, along with the checksums of deployed programs used to pin
+/// imports offline.
 #[wasm_bindgen]
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct OfflineQuery {
     state_paths: IndexMap<Field<CurrentNetwork>, StatePath<CurrentNetwork>>,
     state_root: <CurrentNetwork as Network>::StateRoot,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    program_checksums: IndexMap<ProgramID<CurrentNetwork>, String>,
 }
 
 #[wasm_bindgen]
@@ -41,7 +47,7 @@ impl OfflineQuery {
     #[wasm_bindgen(constructor)]
     pub fn new(state_root: &str) -> Result<OfflineQuery, String> {
         let state_root = <CurrentNetwork as Network>::StateRoot::from_str(state_root).map_err(|e| e.to_string())?;
-        Ok(Self { state_paths: IndexMap::new(), state_root })
+        Ok(Self { state_paths: IndexMap::new(), state_root, program_checksums: IndexMap::new() })
     }
 
     /// Add a new state path to the offline query object.
@@ -56,6 +62,18 @@ impl OfflineQuery {
         Ok(())
     }
 
+    /// Add the checksum of a deployed program, as returned by `Program.checksum`, so imports can be
+    /// pinned to the deployed program offline
+    ///
+    /// @param {string} program_id The id of the deployed program
+    /// @param {string} checksum The checksum of the deployed program
+    #[wasm_bindgen(js_name = "addProgramChecksum")]
+    pub fn add_program_checksum(&mut self, program_id: &str, checksum: &str) -> Result<(), String> {
+        let program_id = ProgramID::from_str(program_id).map_err(|e| e.to_string())?;
+        self.program_checksums.insert(program_id, checksum.to_lowercase());
+        Ok(())
+    }
+
     /// Get a json string representation of the offline query object
     #[wasm_bindgen(js_name = "toString")]
     #[allow(clippy::inherent_to_string)]
@@ -95,6 +113,16 @@ impl QueryTrait<CurrentNetwork> for OfflineQuery {
     }
 }
 
+#[async_trait(?Send)]
+impl DeployedProgramQuery for OfflineQuery {
+    async fn deployed_checksum(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        self.program_checksums
+            .get(program_id)
+            .cloned()
+            .ok_or_else(|| format!("The offline query has no checksum for program {program_id}"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,6 +144,19 @@ mod tests {
         assert_eq!(offline_query_from_str, offline_query);
     }
 
+    #[wasm_bindgen_test]
+    async fn test_program_checksums() {
+        let mut offline_query = OfflineQuery::new(STATE_ROOT).unwrap();
+        let program_id = ProgramIDNative::from_str("hello.aleo").unwrap();
+        assert!(offline_query.deployed_checksum(&program_id).await.is_err());
+
+        // Checksums are normalized and survive serialization
+        offline_query.add_program_checksum("hello.aleo", &"AB".repeat(32)).unwrap();
+        let offline_query = OfflineQuery::from_string(&offline_query.to_string()).unwrap();
+        assert_eq!(offline_query.deployed_checksum(&program_id).await.unwrap(), "ab".repeat(32));
+        assert!(offline_query.add_program_checksum("not a program", "ab").is_err());
+    }
+
     /*#[wasm_bindgen_test]
     async fn test_state_path_construction() {
         // Create an offline query
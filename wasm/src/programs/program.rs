@@ -16,6 +16,7 @@
 
 use crate::{
     account::Address,
+    programs::{generate_typescript, program_checksum, ProgramAbi},
     types::native::{CurrentNetwork, EntryType, IdentifierNative, PlaintextType, ProgramNative, ValueType},
 };
 
@@ -405,6 +406,37 @@ impl Program {
         Ok(struct_members)
     }
 
+    /// Get a complete, versioned JSON description of the program interface. The description
+    /// contains every function with its inputs, outputs and finalize block (including the
+    /// mappings it reads and writes), every closure, struct, record and mapping, and the imports
+    /// of the program. Type information is given in a structured form so that forms and decoders
+    /// can be generated from it.
+    ///
+    /// @returns {string | Error} JSON string describing the program interface
+    ///
+    /// @example
+    /// const credits_program = aleo_wasm.Program.getCreditsProgram();
+    /// const abi = JSON.parse(credits_program.toAbi());
+    /// console.log(abi.functions.find((f) => f.name === "transfer_public").finalize.writes); // ["account"]
+    #[wasm_bindgen(js_name = "toAbi")]
+    pub fn to_abi(&self) -> Result<String, String> {
+        serde_json::to_string(&ProgramAbi::from_program(&self.0)).map_err(|e| e.to_string())
+    }
+
+    /// Generate a TypeScript module describing the program. The module contains an interface for
+    /// every struct and record, a typed input tuple for every function and a decoder for the
+    /// outputs of every function.
+    ///
+    /// @returns {string} TypeScript source of the generated module
+    ///
+    /// @example
+    /// const credits_program = aleo_wasm.Program.getCreditsProgram();
+    /// fs.writeFileSync("credits.ts", credits_program.toTypeScript());
+    #[wasm_bindgen(js_name = "toTypeScript")]
+    pub fn to_typescript(&self) -> String {
+        generate_typescript(&self.0)
+    }
+
     /// Get the credits.aleo program
     ///
     /// @returns {Program} The credits.aleo program
@@ -421,6 +453,15 @@ impl Program {
         self.0.id().to_string()
     }
 
+    /// Get the checksum of the program, the hex encoded SHA-256 digest of its byte representation.
+    /// The checksum can be used to pin imports to the exact program deployed on chain
+    ///
+    /// @returns {string | Error} The checksum of the program
+    #[wasm_bindgen]
+    pub fn checksum(&self) -> Result<String, String> {
+        program_checksum(&self.0)
+    }
+
     /// Get a unique address of the program
     ///
     /// @returns {Address} The address of the program
@@ -836,6 +877,69 @@ function add_and_double:
         assert_eq!(program_native, native_from_program);
     }
 
+    #[wasm_bindgen_test]
+    fn test_checksum() {
+        let credits = Program::get_credits_program();
+        let checksum = credits.checksum().unwrap();
+        assert_eq!(checksum, Program::from_string(&credits.to_string()).unwrap().checksum().unwrap());
+        assert_ne!(checksum, Program::from_string(TOKEN_ISSUE).unwrap().checksum().unwrap());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_to_abi() {
+        use crate::programs::abi::*;
+
+        let abi: ProgramAbi = serde_json::from_str(&Program::get_credits_program().to_abi().unwrap()).unwrap();
+        assert_eq!(abi.version, ABI_VERSION);
+        assert_eq!(abi.program, "credits.aleo");
+        assert!(abi.imports.is_empty());
+
+        let transfer_public = abi.function("transfer_public").unwrap();
+        assert_eq!(transfer_public.inputs, vec![
+            InputAbi {
+                register: "r0".to_string(),
+                r#type: ValueTypeAbi::Public { r#type: PlaintextTypeAbi::Literal { r#type: "address".to_string() } },
+            },
+            InputAbi {
+                register: "r1".to_string(),
+                r#type: ValueTypeAbi::Public { r#type: PlaintextTypeAbi::Literal { r#type: "u64".to_string() } },
+            },
+        ]);
+        assert_eq!(transfer_public.outputs, vec![ValueTypeAbi::Future {
+            locator: "credits.aleo/transfer_public".to_string()
+        }]);
+        let finalize = transfer_public.finalize.as_ref().unwrap();
+        assert_eq!(finalize.reads, vec!["account".to_string()]);
+        assert_eq!(finalize.writes, vec!["account".to_string()]);
+
+        // Ensure functions without a finalize block, records and structs are described
+        let abi = ProgramAbi::from_program(&Program::from_string(TOKEN_ISSUE).unwrap());
+        assert!(abi.function("issue").unwrap().finalize.is_none());
+        assert_eq!(abi.function("issue").unwrap().outputs, vec![ValueTypeAbi::Record { record: "Token".to_string() }]);
+        let record = abi.record("Token").unwrap();
+        assert_eq!(record.owner, "private");
+        assert_eq!(record.entries[2].name, "token_data");
+        assert_eq!(record.entries[2].r#type, PlaintextTypeAbi::Struct { name: "token_metadata".to_string() });
+        assert_eq!(abi.get_struct("token_metadata").unwrap().members.len(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_to_typescript() {
+        let module = Program::get_credits_program().to_typescript();
+        assert!(module.contains("export const PROGRAM_ID = \"credits.aleo\";"));
+        assert!(module.contains("export interface CreditsRecord {\n    owner: string;\n    microcredits: bigint;"));
+        assert!(
+            module.contains(
+                "export type TransferPublicInputs = [\n    r0: `aleo1${string}`,\n    r1: `${bigint}u64`,\n];"
+            )
+        );
+        assert!(module.contains("export type TransferPublicOutputs = [string];"));
+        assert!(
+            module
+                .contains("export function decodeTransferPrivateOutputs(outputs: string[]): TransferPrivateOutputs {")
+        );
+    }
+
     #[wasm_bindgen_test]
     fn test_get_imports() {
         let program = Program::from_string(NESTED_IMPORT_PROGRAM).unwrap();
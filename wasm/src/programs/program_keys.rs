@@ -0,0 +1,201 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{KeyBundle, KeyPair};
+
+use crate::types::native::{
+    FromBytes,
+    IdentifierNative,
+    ProgramIDNative,
+    ProvingKeyNative,
+    ToBytes,
+    VerifyingKeyNative,
+};
+use js_sys::{Array, Object, Reflect};
+use std::{
+    io::{Error, ErrorKind, Result as IoResult},
+    str::FromStr,
+};
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+/// The proving and verifying key of a function along with the size of its circuit
+#[derive(Clone, Debug)]
+pub struct FunctionKeys {
+    pub program_id: ProgramIDNative,
+    pub function_id: IdentifierNative,
+    pub constraints: u64,
+    pub variables: u64,
+    pub proving_key: ProvingKeyNative,
+    pub verifying_key: VerifyingKeyNative,
+}
+
+impl FunctionKeys {
+    /// Pair the keys of a function, reading the size of its circuit from the verifying key
+    pub fn new(
+        program_id: ProgramIDNative,
+        function_id: IdentifierNative,
+        proving_key: ProvingKeyNative,
+        verifying_key: VerifyingKeyNative,
+    ) -> Self {
+        let constraints = verifying_key.circuit_info.num_constraints as u64;
+        let variables = verifying_key.circuit_info.num_public_and_private_variables as u64;
+        Self { program_id, function_id, constraints, variables, proving_key, verifying_key }
+    }
+
+    /// Bundle the keys of the function with their checksums, network and snarkVM version
+    pub fn to_bundle(&self) -> Result<KeyBundle, String> {
+        KeyBundle::from_native(self.program_id, self.function_id, &self.proving_key, &self.verifying_key)
+    }
+
+    /// Read the keys of a function from a key bundle, checking the integrity of the bundle first
+    pub fn from_bundle(bundle: &KeyBundle) -> Result<Self, String> {
+        bundle.verify()?;
+        let (program_id, function_id) = bundle.locator();
+        let (proving_key, verifying_key) = bundle.native_keys()?;
+        Ok(Self::new(program_id, function_id, proving_key, verifying_key))
+    }
+}
+
+/// The keys of every function of a program and of its imports, which can be serialized and
+/// published alongside a deployment. The keys of each function are serialized as a KeyBundle, so
+/// reading a bundle checks the network, snarkVM version and checksums of every key
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ProgramKeys {
+    functions: Vec<FunctionKeys>,
+}
+
+#[wasm_bindgen]
+impl ProgramKeys {
+    /// Get the locators of the functions in the bundle, such as "hello.aleo/main"
+    ///
+    /// @returns {Array} The locators of the functions, imports first
+    pub fn functions(&self) -> Array {
+        self.functions
+            .iter()
+            .map(|keys| JsValue::from_str(&format!("{}/{}", keys.program_id, keys.function_id)))
+            .collect()
+    }
+
+    /// Get the size of the circuit of every function in the bundle
+    ///
+    /// @returns {Object | Error} An object of the form \{ "hello.aleo/main": \{ constraints: number, variables: number \} \}
+    #[wasm_bindgen(js_name = constraintCounts)]
+    pub fn constraint_counts(&self) -> Result<Object, String> {
+        let counts = Object::new();
+        for keys in self.functions.iter() {
+            let count = Object::new();
+            Reflect::set(&count, &"constraints".into(), &JsValue::from(keys.constraints as f64))
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&count, &"variables".into(), &JsValue::from(keys.variables as f64))
+                .map_err(|_| "Failed to set property")?;
+            Reflect::set(&counts, &&format!("{}/{}", keys.program_id, keys.function_id).into(), &count.into())
+                .map_err(|_| "Failed to set property")?;
+        }
+        Ok(counts)
+    }
+
+    /// Get the keys of a function in the bundle
+    ///
+    /// @param {string} program_id The id of the program
+    /// @param {string} function The name of the function
+    /// @returns {KeyPair | Error} The keys of the function
+    #[wasm_bindgen(js_name = getKeys)]
+    pub fn get_keys(&self, program_id: &str, function: &str) -> Result<KeyPair, String> {
+        let keys = self.function_keys(program_id, function)?;
+        Ok(KeyPair::from((keys.proving_key.clone(), keys.verifying_key.clone())))
+    }
+
+    /// Serialize the bundle to bytes
+    ///
+    /// @returns {Uint8Array | Error} The byte representation of the bundle
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![Self::VERSION];
+        (self.functions.len() as u32).write_le(&mut bytes).map_err(|e| e.to_string())?;
+        for keys in self.functions.iter() {
+            keys.to_bundle()?
+                .write_le(&mut bytes)
+                .map_err(|_| format!("Failed to serialize the keys of {}", keys.function_id))?;
+        }
+        Ok(bytes)
+    }
+
+    /// Deserialize a bundle from bytes
+    ///
+    /// @param {Uint8Array} bytes The byte representation of the bundle
+    /// @returns {ProgramKeys | Error} The bundle
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<ProgramKeys, String> {
+        let mut reader = bytes;
+        let read = |reader: &mut &[u8]| -> IoResult<Vec<KeyBundle>> {
+            let version = u8::read_le(&mut *reader)?;
+            if version != Self::VERSION {
+                return Err(Error::new(ErrorKind::InvalidData, format!("Unsupported key bundle version {version}")));
+            }
+            let count = u32::read_le(&mut *reader)?;
+            (0..count).map(|_| KeyBundle::read_le(&mut *reader)).collect()
+        };
+        let bundles = read(&mut reader).map_err(|e| format!("Failed to deserialize key bundle: {e}"))?;
+        if !reader.is_empty() {
+            return Err("Failed to deserialize key bundle: unexpected trailing bytes".to_string());
+        }
+        let functions = bundles.iter().map(FunctionKeys::from_bundle).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { functions })
+    }
+}
+
+impl ProgramKeys {
+    const VERSION: u8 = 1;
+
+    /// Create a bundle from the keys of a set of functions
+    pub fn new(functions: Vec<FunctionKeys>) -> Self {
+        Self { functions }
+    }
+
+    /// Get the keys of every function in the bundle
+    pub fn keys(&self) -> &[FunctionKeys] {
+        &self.functions
+    }
+
+    /// Get the keys of a function in the bundle
+    pub fn function_keys(&self, program_id: &str, function: &str) -> Result<&FunctionKeys, String> {
+        let program_id = ProgramIDNative::from_str(program_id).map_err(|e| e.to_string())?;
+        let function_id = IdentifierNative::from_str(function).map_err(|e| e.to_string())?;
+        self.functions
+            .iter()
+            .find(|keys| keys.program_id == program_id && keys.function_id == function_id)
+            .ok_or_else(|| format!("The key bundle does not contain keys for {program_id}/{function_id}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn test_program_keys_encoding() {
+        let empty = ProgramKeys::from_bytes(&[1, 0, 0, 0, 0]).unwrap();
+        assert_eq!(empty.functions().length(), 0);
+        assert_eq!(empty.to_bytes().unwrap(), [1, 0, 0, 0, 0]);
+
+        assert!(ProgramKeys::from_bytes(&[2, 0, 0, 0, 0]).unwrap_err().contains("version"));
+        assert!(ProgramKeys::from_bytes(&[1, 0, 0, 0, 0, 0]).unwrap_err().contains("trailing"));
+        assert!(ProgramKeys::from_bytes(&[1, 1, 0, 0, 0]).is_err());
+        assert!(empty.get_keys("hello.aleo", "main").is_err());
+    }
+}
@@ -0,0 +1,257 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use js_sys::{Promise, Reflect, Uint8Array};
+use std::{
+    collections::VecDeque,
+    io::{Read, Result as IoResult},
+};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+#[wasm_bindgen]
+extern "C" {
+    /// A javascript ReadableStream of bytes, such as the body of a fetch response
+    #[wasm_bindgen(typescript_type = "ReadableStream<Uint8Array>")]
+    pub type ByteStream;
+
+    #[wasm_bindgen(method, js_name = getReader)]
+    fn get_reader(this: &ByteStream) -> ByteStreamReader;
+
+    type ByteStreamReader;
+
+    #[wasm_bindgen(method)]
+    fn read(this: &ByteStreamReader) -> Promise;
+
+    #[wasm_bindgen(method, js_name = releaseLock)]
+    fn release_lock(this: &ByteStreamReader);
+}
+
+// A reader over a queue of chunks which frees every chunk once it has been read.
+struct ChunkReader {
+    chunks: VecDeque<Vec<u8>>,
+    position: usize,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buffer: &mut [u8]) -> IoResult<usize> {
+        let chunk = match self.chunks.front() {
+            Some(chunk) => chunk,
+            None => return Ok(0),
+        };
+        let length = buffer.len().min(chunk.len() - self.position);
+        buffer[..length].copy_from_slice(&chunk[self.position..self.position + length]);
+        self.position += length;
+        if self.position == chunk.len() {
+            self.chunks.pop_front();
+            self.position = 0;
+        }
+        Ok(length)
+    }
+}
+
+/// Incremental loader building a proving key from chunks of its bytes.
+///
+/// ProvingKey.fromBytes needs the whole key copied into wasm memory while javascript still holds
+/// its own copy, which can exhaust the 4GB wasm memory limit for large keys. The loader instead
+/// takes the key one chunk at a time, so javascript can drop every chunk once it is pushed, and
+/// frees each chunk as soon as it has been deserialized. The checksum of the key is computed while
+/// the chunks arrive.
+#[wasm_bindgen]
+pub struct ProvingKeyLoader {
+    chunks: VecDeque<Vec<u8>>,
+    bytes_loaded: usize,
+    hasher: sha2::Sha256,
+}
+
+#[wasm_bindgen]
+impl ProvingKeyLoader {
+    /// Create an empty proving key loader
+    ///
+    /// @returns {ProvingKeyLoader}
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> ProvingKeyLoader {
+        Self { chunks: VecDeque::new(), bytes_loaded: 0, hasher: sha2::Sha256::new() }
+    }
+
+    /// Append the next chunk of the proving key bytes
+    ///
+    /// @param {Uint8Array} chunk The next bytes of the proving key
+    #[wasm_bindgen]
+    pub fn push(&mut self, chunk: Vec<u8>) {
+        if chunk.is_empty() {
+            return;
+        }
+        self.hasher.update(&chunk);
+        self.bytes_loaded += chunk.len();
+        self.chunks.push_back(chunk);
+    }
+
+    /// Get the number of bytes pushed so far
+    ///
+    /// @returns {number} The number of bytes pushed
+    #[wasm_bindgen(js_name = bytesLoaded)]
+    pub fn bytes_loaded(&self) -> usize {
+        self.bytes_loaded
+    }
+
+    /// Get the checksum of the bytes pushed so far, which is the checksum of the proving key once
+    /// every chunk has been pushed
+    ///
+    /// @returns {string} The checksum of the bytes pushed
+    #[wasm_bindgen]
+    pub fn checksum(&self) -> String {
+        hex::encode(self.hasher.clone().finalize())
+    }
+
+    /// Deserialize the proving key from the pushed chunks, freeing the chunks as they are read.
+    /// This consumes the loader
+    ///
+    /// @param {string | undefined} expected_checksum (optional) The checksum the key must have,
+    /// verified before the key is deserialized
+    /// @returns {ProvingKey | Error} The proving key
+    #[wasm_bindgen]
+    pub fn finish(self, expected_checksum: Option<String>) -> Result<ProvingKey, String> {
+        if let Some(expected_checksum) = expected_checksum {
+            let checksum = self.checksum();
+            if checksum != expected_checksum.to_lowercase() {
+                return Err(format!("The proving key has checksum {checksum}, expected {expected_checksum}"));
+            }
+        }
+        let mut reader = ChunkReader { chunks: self.chunks, position: 0 };
+        let proving_key =
+            ProvingKeyNative::read_le(&mut reader).map_err(|e| format!("Failed to deserialize proving key: {e}"))?;
+        if !reader.chunks.is_empty() {
+            return Err("Failed to deserialize proving key: unexpected trailing bytes".to_string());
+        }
+        Ok(ProvingKey::from(proving_key))
+    }
+
+    /// Load a proving key from a stream of bytes, such as the body of a fetch response, without
+    /// holding the whole key in javascript memory
+    ///
+    /// @param {ReadableStream<Uint8Array>} stream The stream of the proving key bytes
+    /// @param {string | undefined} expected_checksum (optional) The checksum the key must have
+    /// @returns {Promise<ProvingKey>} The proving key
+    #[wasm_bindgen(js_name = fromStream)]
+    pub async fn from_stream(stream: ByteStream, expected_checksum: Option<String>) -> Result<ProvingKey, String> {
+        let reader = stream.get_reader();
+        let mut loader = Self::new();
+        // Release the lock even if reading fails, so the stream can still be cancelled by its owner
+        let result = loader.read_stream(&reader).await;
+        reader.release_lock();
+        result?;
+        loader.finish(expected_checksum)
+    }
+}
+
+impl ProvingKeyLoader {
+    // Push every chunk of a stream into the loader.
+    async fn read_stream(&mut self, reader: &ByteStreamReader) -> Result<(), String> {
+        let error = |e: JsValue| e.as_string().unwrap_or_else(|| format!("Failed to read the stream: {e:?}"));
+        loop {
+            let next = JsFuture::from(reader.read()).await.map_err(error)?;
+            if Reflect::get(&next, &JsValue::from_str("done")).map_err(error)?.is_truthy() {
+                return Ok(());
+            }
+            let chunk = Reflect::get(&next, &JsValue::from_str("value")).map_err(error)?;
+            self.push(Uint8Array::new(&chunk).to_vec());
+        }
+    }
+}
+
+impl Default for ProvingKeyLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::native::{CurrentAleo, IdentifierNative, ProcessNative, ProgramNative};
+
+    use rand::{rngs::StdRng, SeedableRng};
+    use wasm_bindgen_test::*;
+
+    const PROGRAM: &str = r#"program key_loader_test.aleo;
+
+function add:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    add r0 r1 into r2;
+    output r2 as u32.private;
+"#;
+
+    fn proving_key() -> ProvingKey {
+        let program = ProgramNative::from_str(PROGRAM).unwrap();
+        let function_id = IdentifierNative::from_str("add").unwrap();
+        let mut process = ProcessNative::load_web().unwrap();
+        process.add_program(&program).unwrap();
+        process.synthesize_key::<CurrentAleo, _>(program.id(), &function_id, &mut StdRng::from_entropy()).unwrap();
+        ProvingKey::from(process.get_proving_key(program.id(), function_id).unwrap())
+    }
+
+    // Create a ReadableStream enqueuing chunks of bytes, then closing or failing with an error.
+    fn byte_stream(bytes: &[u8], chunk_size: usize, error: Option<&str>) -> ByteStream {
+        let chunks = bytes.chunks(chunk_size).map(Uint8Array::from).collect::<js_sys::Array>();
+        let stream = js_sys::Function::new_with_args(
+            "chunks, error",
+            "return new ReadableStream({ start(controller) { chunks.forEach((chunk) => controller.enqueue(chunk)); error ? controller.error(error) : controller.close() } })",
+        );
+        let error = error.map_or(JsValue::UNDEFINED, JsValue::from_str);
+        ByteStream::from(stream.call2(&JsValue::NULL, &chunks, &error).unwrap())
+    }
+
+    fn load(bytes: &[u8], chunk_size: usize) -> ProvingKeyLoader {
+        let mut loader = ProvingKeyLoader::new();
+        bytes.chunks(chunk_size).for_each(|chunk| loader.push(chunk.to_vec()));
+        loader
+    }
+
+    #[wasm_bindgen_test]
+    fn test_proving_key_loader() {
+        let key = proving_key();
+        let bytes = key.to_bytes().unwrap();
+
+        let loader = load(&bytes, 1000);
+        assert_eq!(loader.bytes_loaded(), bytes.len());
+        assert_eq!(loader.checksum(), key.checksum());
+        assert_eq!(loader.finish(Some(key.checksum())).unwrap(), key);
+        assert_eq!(load(&bytes, 7).finish(None).unwrap(), key);
+
+        assert!(load(&bytes, 1000).finish(Some("00".repeat(32))).unwrap_err().contains("expected"));
+        assert!(load(&bytes[..bytes.len() - 1], 1000).finish(None).is_err());
+        assert!(load(&[bytes.as_slice(), &[0]].concat(), 1000).finish(None).unwrap_err().contains("trailing"));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_proving_key_from_stream() {
+        let key = proving_key();
+        let bytes = key.to_bytes().unwrap();
+
+        let loaded = ProvingKeyLoader::from_stream(byte_stream(&bytes, 1000, None), Some(key.checksum())).await;
+        assert_eq!(loaded.unwrap(), key);
+
+        // A failing stream is reported and its lock is released
+        let stream = byte_stream(&bytes[..1000], 100, Some("connection reset"));
+        let error = ProvingKeyLoader::from_stream(stream.clone(), None).await.unwrap_err();
+        assert_eq!(error, "connection reset");
+        assert!(!Reflect::get(&stream, &JsValue::from_str("locked")).unwrap().is_truthy());
+    }
+}
@@ -15,6 +15,7 @@
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
 mod credits;
+mod loader;
 
 use crate::types::native::{FromBytes, ProvingKeyNative, ToBytes};
 
@@ -23,6 +24,8 @@ use wasm_bindgen::prelude::wasm_bindgen;
 
 use std::{ops::Deref, str::FromStr};
 
+pub use loader::{ByteStream, ProvingKeyLoader};
+
 /// Proving key for a function within an Aleo program
 #[wasm_bindgen]
 #[derive(Clone, Debug)]
@@ -45,7 +48,8 @@ impl ProvingKey {
         self.0.clone().into()
     }
 
-    /// Construct a new proving key from a byte array
+    /// Construct a new proving key from a byte array. Large keys can be loaded with less memory
+    /// from chunks or streams with ProvingKeyLoader
     ///
     /// @param {Uint8Array} bytes Byte array representation of a proving key
     /// @returns {ProvingKey | Error}
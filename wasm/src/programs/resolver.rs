@@ -0,0 +1,548 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::types::native::{
+    CurrentNetwork,
+    Network,
+    ProcessNative,
+    ProgramIDNative,
+    ProgramNative,
+    QueryNative,
+    ToBytes,
+};
+use snarkvm_console::network::{MainnetV0, TestnetV0};
+
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use js_sys::{Object, Reflect};
+use sha2::Digest;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+use std::{cell::RefCell, str::FromStr};
+
+/// A source of program code used to resolve the imports of a program.
+///
+/// Implementations return the source code of the requested program or an error explaining why it
+/// could not be found. The [`ImportResolver`] wraps a source to walk a program's imports, check
+/// that every resolved program has the requested id and cache the results.
+#[async_trait(?Send)]
+pub trait ProgramResolver {
+    /// Get the source code of a program
+    async fn program_source(&self, program_id: &ProgramIDNative) -> Result<String, String>;
+}
+
+/// A query for the programs deployed on chain, implemented by the query types used to build
+/// transactions (such as `QueryNative` and `OfflineQuery`) so that imports can be pinned to the
+/// programs the transaction will be verified against
+#[async_trait(?Send)]
+pub trait DeployedProgramQuery {
+    /// Get the checksum of a deployed program, as computed by [`program_checksum`]
+    async fn deployed_checksum(&self, program_id: &ProgramIDNative) -> Result<String, String>;
+}
+
+#[async_trait(?Send)]
+impl DeployedProgramQuery for QueryNative {
+    async fn deployed_checksum(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        let program = self
+            .get_program_async(program_id)
+            .await
+            .map_err(|e| format!("Failed to fetch the deployed program {program_id}: {e}"))?;
+        program_checksum(&program)
+    }
+}
+
+/// A program source backed by an in-memory map from program ids to source code
+#[derive(Clone, Debug, Default)]
+pub struct MemoryResolver {
+    programs: IndexMap<String, String>,
+}
+
+impl MemoryResolver {
+    /// Create an empty in-memory program source
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an in-memory program source from a javascript object of the form
+    /// { "program.aleo": "program source code" }
+    pub fn from_object(imports: &Object) -> Result<Self, String> {
+        let mut source = Self::new();
+        for key in Object::keys(imports).iter() {
+            let program_id = key.as_string().ok_or_else(|| "Import names must be strings".to_string())?;
+            let program = Reflect::get(imports, &key)
+                .map_err(|_| format!("Failed to read the import {program_id}"))?
+                .as_string()
+                .ok_or_else(|| format!("The source code of the import {program_id} must be a string"))?;
+            source.insert(&program_id, &program);
+        }
+        Ok(source)
+    }
+
+    /// Add the source code of a program
+    pub fn insert(&mut self, program_id: &str, program: &str) {
+        self.programs.insert(program_id.to_string(), program.to_string());
+    }
+
+    /// Get the source code of a program without awaiting
+    pub fn get(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        self.programs
+            .get(&program_id.to_string())
+            .cloned()
+            .ok_or_else(|| format!("Program import {program_id} was not found in the imports provided"))
+    }
+}
+
+#[async_trait(?Send)]
+impl ProgramResolver for MemoryResolver {
+    async fn program_source(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        self.get(program_id)
+    }
+}
+
+/// A program source reading `<program_id>` files (such as `hello.aleo`) from a local directory.
+/// This source requires filesystem access and is only available in native builds
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct DirectoryResolver {
+    directory: PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DirectoryResolver {
+    /// Create a program source reading programs from a directory
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self { directory: directory.into() }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait(?Send)]
+impl ProgramResolver for DirectoryResolver {
+    async fn program_source(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        let path = self.directory.join(program_id.to_string());
+        std::fs::read_to_string(&path)
+            .map_err(|e| format!("Program import {program_id} could not be read from {}: {e}", path.display()))
+    }
+}
+
+/// A program source fetching deployed programs from the REST API of an Aleo node. Any server
+/// exposing the `/{network}/program/{program_id}` endpoint of the current network (such as
+/// `/testnet/program/hello.aleo`) can be used, including local mock servers
+#[derive(Clone, Debug)]
+pub struct NetworkResolver {
+    url: String,
+}
+
+impl NetworkResolver {
+    /// Create a program source fetching programs from the node at the given base url
+    pub fn new(url: &str) -> Self {
+        Self { url: url.trim_end_matches('/').to_string() }
+    }
+}
+
+#[async_trait(?Send)]
+impl ProgramResolver for NetworkResolver {
+    async fn program_source(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        let endpoint = format!("{}/{}/program/{program_id}", self.url, network_route()?);
+        let response = reqwest::get(&endpoint)
+            .await
+            .map_err(|e| format!("Failed to fetch program import {program_id} from {endpoint}: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Failed to fetch program import {program_id} from {endpoint}: the node responded with {}",
+                response.status()
+            ));
+        }
+        let body = response.text().await.map_err(|e| e.to_string())?;
+        // Nodes return the program as a json string, fall back to the raw body otherwise
+        Ok(serde_json::from_str::<String>(&body).unwrap_or(body))
+    }
+}
+
+// Get the name of the current network in the routes of the REST API of Aleo nodes.
+fn network_route() -> Result<&'static str, String> {
+    match CurrentNetwork::ID {
+        MainnetV0::ID => Ok("mainnet"),
+        TestnetV0::ID => Ok("testnet"),
+        id => Err(format!("The network with id {id} has no known REST API routes")),
+    }
+}
+
+/// A program source which tries a primary source first and falls back to a second source when
+/// the primary source cannot provide a program
+#[derive(Clone, Debug)]
+pub struct FallbackResolver<A, B> {
+    primary: A,
+    fallback: B,
+}
+
+impl<A: ProgramResolver, B: ProgramResolver> FallbackResolver<A, B> {
+    /// Create a program source trying `primary` before `fallback`
+    pub fn new(primary: A, fallback: B) -> Self {
+        Self { primary, fallback }
+    }
+}
+
+#[async_trait(?Send)]
+impl<A: ProgramResolver, B: ProgramResolver> ProgramResolver for FallbackResolver<A, B> {
+    async fn program_source(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+        match self.primary.program_source(program_id).await {
+            Ok(program) => Ok(program),
+            Err(primary) => {
+                self.fallback.program_source(program_id).await.map_err(|fallback| format!("{primary}; {fallback}"))
+            }
+        }
+    }
+}
+
+/// Resolves the imports of programs from a program source, caching every resolved program.
+///
+/// Imports can be pinned to a program checksum, in which case a resolved program whose checksum
+/// differs is rejected before it can be added to a process.
+pub struct ImportResolver<R: ProgramResolver> {
+    source: R,
+    cache: RefCell<IndexMap<ProgramIDNative, ProgramNative>>,
+    pins: IndexMap<ProgramIDNative, String>,
+}
+
+impl<R: ProgramResolver> ImportResolver<R> {
+    /// Create an import resolver for a program source
+    pub fn new(source: R) -> Self {
+        Self { source, cache: RefCell::new(IndexMap::new()), pins: IndexMap::new() }
+    }
+
+    /// Pin a program to a checksum computed with [`program_checksum`]
+    pub fn pin(&mut self, program_id: ProgramIDNative, checksum: &str) {
+        self.pins.insert(program_id, checksum.to_lowercase());
+    }
+
+    /// Pin programs to the checksums of a javascript object of the form { "program.aleo": "checksum" }
+    pub fn pin_object(&mut self, checksums: &Object) -> Result<(), String> {
+        for key in Object::keys(checksums).iter() {
+            let program_id = key.as_string().ok_or_else(|| "Import names must be strings".to_string())?;
+            let checksum = Reflect::get(checksums, &key)
+                .ok()
+                .and_then(|checksum| checksum.as_string())
+                .ok_or_else(|| format!("The checksum of the import {program_id} must be a string"))?;
+            let program_id = ProgramIDNative::from_str(&program_id).map_err(|err| err.to_string())?;
+            self.pin(program_id, &checksum);
+        }
+        Ok(())
+    }
+
+    /// Pin a program to the checksum of the program deployed on chain, as reported by a query
+    pub async fn pin_onchain(
+        &mut self,
+        program_id: ProgramIDNative,
+        query: &impl DeployedProgramQuery,
+    ) -> Result<(), String> {
+        let checksum = query.deployed_checksum(&program_id).await?;
+        self.pin(program_id, &checksum);
+        Ok(())
+    }
+
+    /// Check whether a program is pinned to a checksum
+    pub fn is_pinned(&self, program_id: &ProgramIDNative) -> bool {
+        self.pins.contains_key(program_id)
+    }
+
+    /// Check that a program matches the checksum it is pinned to, if any
+    pub fn check_pin(&self, program: &ProgramNative) -> Result<(), String> {
+        if let Some(expected) = self.pins.get(program.id()) {
+            let checksum = program_checksum(program)?;
+            if &checksum != expected {
+                return Err(format!(
+                    "Program import {} has checksum {checksum} but is pinned to checksum {expected}",
+                    program.id()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the programs resolved so far
+    pub fn cached(&self) -> Vec<ProgramNative> {
+        self.cache.borrow().values().cloned().collect()
+    }
+
+    /// Resolve a single program, checking that the source returned the program requested
+    pub async fn resolve(&self, program_id: &ProgramIDNative) -> Result<ProgramNative, String> {
+        if let Some(program) = self.cache.borrow().get(program_id) {
+            self.check_pin(program)?;
+            return Ok(program.clone());
+        }
+        let source = self.source.program_source(program_id).await?;
+        let program = parse_import(program_id, &source)?;
+        self.check_pin(&program)?;
+        self.cache.borrow_mut().insert(*program_id, program.clone());
+        Ok(program)
+    }
+
+    /// Resolve the imports of a program in dependency order, so that every program appears after
+    /// the programs it imports. Imports for which `is_loaded` returns true (along with their own
+    /// imports) are skipped, as is `credits.aleo`
+    pub async fn resolve_imports(
+        &self,
+        program: &ProgramNative,
+        is_loaded: impl Fn(&ProgramIDNative) -> bool,
+    ) -> Result<Vec<ProgramNative>, String> {
+        let mut resolved = IndexMap::<ProgramIDNative, ProgramNative>::new();
+        // Walk the import graph depth first, keeping the index of the next import to visit
+        let mut stack = vec![(program.clone(), 0usize)];
+        while let Some((current, index)) = stack.pop() {
+            match current.imports().keys().nth(index).copied() {
+                Some(import_id) => {
+                    stack.push((current, index + 1));
+                    if import_id.to_string() == "credits.aleo"
+                        || resolved.contains_key(&import_id)
+                        || is_loaded(&import_id)
+                    {
+                        continue;
+                    }
+                    if stack.iter().any(|(program, _)| program.id() == &import_id) {
+                        return Err(format!("Program import {import_id} is part of an import cycle"));
+                    }
+                    stack.push((self.resolve(&import_id).await?, 0));
+                }
+                None if !stack.is_empty() => {
+                    resolved.insert(*current.id(), current);
+                }
+                None => {}
+            }
+        }
+        Ok(resolved.into_values().collect())
+    }
+
+    /// Resolve the imports of a program and add any that are missing to a process
+    pub async fn add_imports(&self, process: &mut ProcessNative, program: &ProgramNative) -> Result<(), String> {
+        let imports = self.resolve_imports(program, |program_id| process.contains_program(program_id)).await?;
+        for import in imports {
+            crate::log(&format!("Importing program: {}", import.id()));
+            process.add_program(&import).map_err(|err| err.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Compute the checksum of a program: the hex encoded SHA-256 digest of its byte representation
+pub fn program_checksum(program: &ProgramNative) -> Result<String, String> {
+    let bytes = program.to_bytes_le().map_err(|e| e.to_string())?;
+    Ok(hex::encode(sha2::Sha256::digest(bytes)))
+}
+
+/// Parse the source code of an import and check that it declares the expected program id
+pub fn parse_import(program_id: &ProgramIDNative, source: &str) -> Result<ProgramNative, String> {
+    let program = ProgramNative::from_str(source)
+        .map_err(|e| format!("The source code provided for program import {program_id} is invalid: {e}"))?;
+    if program.id() != program_id {
+        return Err(format!("Program import {program_id} resolved to the source code of {}", program.id()));
+    }
+    Ok(program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use js_sys::Function;
+    use std::cell::Cell;
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::*;
+
+    const MULTIPLY_PROGRAM: &str = r#"program multiply_test.aleo;
+
+function multiply:
+    input r0 as u32.public;
+    input r1 as u32.private;
+    mul r0 r1 into r2;
+    output r2 as u32.private;
+"#;
+
+    const DOUBLE_PROGRAM: &str = r#"import multiply_test.aleo;
+
+program double_test.aleo;
+
+function double_it:
+    input r0 as u32.private;
+    call multiply_test.aleo/multiply 2u32 r0 into r1;
+    output r1 as u32.private;
+"#;
+
+    const QUADRUPLE_PROGRAM: &str = r#"import multiply_test.aleo;
+import double_test.aleo;
+
+program quadruple_test.aleo;
+
+function quadruple_it:
+    input r0 as u32.private;
+    call double_test.aleo/double_it r0 into r1;
+    call multiply_test.aleo/multiply 2u32 r1 into r2;
+    output r2 as u32.private;
+"#;
+
+    // A resolver counting the number of programs requested from it.
+    struct CountingResolver {
+        programs: MemoryResolver,
+        requests: Cell<usize>,
+    }
+
+    #[async_trait(?Send)]
+    impl ProgramResolver for CountingResolver {
+        async fn program_source(&self, program_id: &ProgramIDNative) -> Result<String, String> {
+            self.requests.set(self.requests.get() + 1);
+            self.programs.get(program_id)
+        }
+    }
+
+    fn programs() -> MemoryResolver {
+        let mut programs = MemoryResolver::new();
+        programs.insert("multiply_test.aleo", MULTIPLY_PROGRAM);
+        programs.insert("double_test.aleo", DOUBLE_PROGRAM);
+        programs
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_resolve_imports_in_dependency_order() {
+        let resolver = ImportResolver::new(programs());
+        let program = ProgramNative::from_str(QUADRUPLE_PROGRAM).unwrap();
+
+        let imports = resolver.resolve_imports(&program, |_| false).await.unwrap();
+        let ids = imports.iter().map(|import| import.id().to_string()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["multiply_test.aleo", "double_test.aleo"]);
+
+        let mut process = ProcessNative::load_web().unwrap();
+        resolver.add_imports(&mut process, &program).await.unwrap();
+        process.add_program(&program).unwrap();
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_resolved_programs_are_cached() {
+        let resolver = ImportResolver::new(CountingResolver { programs: programs(), requests: Cell::new(0) });
+        let program = ProgramNative::from_str(QUADRUPLE_PROGRAM).unwrap();
+
+        resolver.resolve_imports(&program, |_| false).await.unwrap();
+        resolver.resolve_imports(&program, |_| false).await.unwrap();
+        assert_eq!(resolver.source.requests.get(), 2);
+        assert_eq!(resolver.cached().len(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_missing_and_mismatched_imports() {
+        let program = ProgramNative::from_str(QUADRUPLE_PROGRAM).unwrap();
+
+        let mut missing = MemoryResolver::new();
+        missing.insert("double_test.aleo", DOUBLE_PROGRAM);
+        let error = ImportResolver::new(missing).resolve_imports(&program, |_| false).await.unwrap_err();
+        assert!(error.contains("multiply_test.aleo was not found"));
+
+        let mut mismatched = programs();
+        mismatched.insert("multiply_test.aleo", DOUBLE_PROGRAM);
+        let error = ImportResolver::new(mismatched).resolve_imports(&program, |_| false).await.unwrap_err();
+        assert!(error.contains("resolved to the source code of double_test.aleo"));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_pinned_imports() {
+        let program = ProgramNative::from_str(QUADRUPLE_PROGRAM).unwrap();
+        let multiply_id = ProgramIDNative::from_str("multiply_test.aleo").unwrap();
+        let multiply = ProgramNative::from_str(MULTIPLY_PROGRAM).unwrap();
+        let checksum = program_checksum(&multiply).unwrap();
+        assert_eq!(checksum.len(), 64);
+
+        let mut resolver = ImportResolver::new(programs());
+        resolver.pin(multiply_id, &checksum.to_uppercase());
+        assert!(resolver.is_pinned(&multiply_id));
+        assert_eq!(resolver.resolve_imports(&program, |_| false).await.unwrap().len(), 2);
+
+        // A cached program is checked against pins added after it was resolved
+        resolver.pin(multiply_id, &"0".repeat(64));
+        let error = resolver.resolve_imports(&program, |_| false).await.unwrap_err();
+        assert!(error.contains("multiply_test.aleo has checksum"));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_fallback_resolver() {
+        let mut primary = MemoryResolver::new();
+        primary.insert("double_test.aleo", DOUBLE_PROGRAM);
+        let mut fallback = MemoryResolver::new();
+        fallback.insert("multiply_test.aleo", MULTIPLY_PROGRAM);
+
+        let resolver = ImportResolver::new(FallbackResolver::new(primary, fallback));
+        let program = ProgramNative::from_str(QUADRUPLE_PROGRAM).unwrap();
+        assert_eq!(resolver.resolve_imports(&program, |_| false).await.unwrap().len(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_network_resolver() {
+        // Serve programs from a mock node by replacing the fetch function used for requests
+        let programs = Object::new();
+        let path = |program_id: &str| JsValue::from_str(&format!("/{}/program/{program_id}", network_route().unwrap()));
+        Reflect::set(&programs, &path("multiply_test.aleo"), &JsValue::from_str(MULTIPLY_PROGRAM)).unwrap();
+        Reflect::set(&programs, &path("double_test.aleo"), &JsValue::from_str(DOUBLE_PROGRAM)).unwrap();
+        let mock = Function::new_with_args(
+            "programs",
+            "return async (request) => {
+                const path = new URL(typeof request === 'string' ? request : request.url).pathname;
+                const program = programs[path];
+                return program === undefined
+                    ? new Response('Not found', { status: 404 })
+                    : new Response(JSON.stringify(program), { status: 200 });
+            };",
+        )
+        .call1(&JsValue::NULL, &programs)
+        .unwrap();
+        let global = js_sys::global();
+        let fetch = Reflect::get(&global, &JsValue::from_str("fetch")).unwrap();
+        Reflect::set(&global, &JsValue::from_str("fetch"), &mock).unwrap();
+
+        let resolver = ImportResolver::new(NetworkResolver::new("http://localhost:3030/"));
+        let program = ProgramNative::from_str(QUADRUPLE_PROGRAM).unwrap();
+        let imports = resolver.resolve_imports(&program, |_| false).await;
+        let missing = resolver.resolve(&ProgramIDNative::from_str("missing.aleo").unwrap()).await;
+        Reflect::set(&global, &JsValue::from_str("fetch"), &fetch).unwrap();
+
+        let ids = imports.unwrap().iter().map(|import| import.id().to_string()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["multiply_test.aleo", "double_test.aleo"]);
+        let error = missing.unwrap_err();
+        assert!(error.contains(&format!("http://localhost:3030/{}/program/missing.aleo", network_route().unwrap())));
+        assert!(error.contains("404"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_directory_resolver() {
+        let directory = std::env::temp_dir().join(format!("aleo_resolver_{}", rand::random::<u64>()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join("multiply_test.aleo"), MULTIPLY_PROGRAM).unwrap();
+        std::fs::write(directory.join("double_test.aleo"), MULTIPLY_PROGRAM).unwrap();
+
+        // Programs are read from files named after their program id
+        let resolver = ImportResolver::new(DirectoryResolver::new(&directory));
+        let multiply_id = ProgramIDNative::from_str("multiply_test.aleo").unwrap();
+        let multiply = futures::executor::block_on(resolver.resolve(&multiply_id)).unwrap();
+        assert_eq!(multiply, ProgramNative::from_str(MULTIPLY_PROGRAM).unwrap());
+
+        // Missing files and files declaring another program are rejected
+        let double_id = ProgramIDNative::from_str("double_test.aleo").unwrap();
+        let error = futures::executor::block_on(resolver.resolve(&double_id)).unwrap_err();
+        assert!(error.contains("resolved to the source code of multiply_test.aleo"));
+        let missing_id = ProgramIDNative::from_str("missing.aleo").unwrap();
+        let error = futures::executor::block_on(resolver.resolve(&missing_id)).unwrap_err();
+        assert!(error.contains("could not be read from"));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
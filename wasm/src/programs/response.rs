@@ -25,7 +25,7 @@ use crate::types::native::{
     VerifyingKeyNative,
 };
 
-use crate::{Execution, KeyPair, Program, ProvingKey, VerifyingKey};
+use crate::{output_to_js, Execution, KeyPair, Program, ProvingKey, VerifyingKey};
 use std::{ops::Deref, str::FromStr};
 use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
@@ -40,7 +40,7 @@ pub struct ExecutionResponse {
     response: ResponseNative,
     program: ProgramNative,
     proving_key: Option<ProvingKeyNative>,
-    verifying_key: VerifyingKeyNative,
+    verifying_key: Option<VerifyingKeyNative>,
 }
 
 #[wasm_bindgen]
@@ -63,7 +63,24 @@ impl ExecutionResponse {
             function_id: IdentifierNative::from_str(function_id).map_err(|e| e.to_string())?,
             program,
             proving_key: None,
-            verifying_key,
+            verifying_key: Some(verifying_key),
+        })
+    }
+
+    /// Create a response for a function that was evaluated without synthesizing its circuit or
+    /// keys, such a response has no execution and no keys
+    pub(crate) fn evaluated(
+        function_id: &str,
+        response: ResponseNative,
+        program: ProgramNative,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            execution: None,
+            response,
+            function_id: IdentifierNative::from_str(function_id).map_err(|e| e.to_string())?,
+            program,
+            proving_key: None,
+            verifying_key: None,
         })
     }
 
@@ -92,6 +109,28 @@ impl ExecutionResponse {
         array
     }
 
+    /// Get the outputs of the executed function decoded into javascript values
+    ///
+    /// Each output is an object of the form \{ type: string, visibility: string, value: any \}
+    /// where `type` is the declared type of the output and `visibility` is one of `constant`,
+    /// `public`, `private`, `record`, `external_record` or `future`. Booleans and integers of up
+    /// to 32 bits are decoded to numbers and booleans, larger integers to bigints, structs to
+    /// objects, arrays to arrays and records to `RecordPlaintext` objects. Futures are decoded to
+    /// \{ programId: string, functionName: string, arguments: Array \}. Other literals are returned
+    /// as strings.
+    ///
+    /// @returns {Array | Error} Array of decoded outputs of the function
+    #[wasm_bindgen(js_name = "getDecodedOutputs")]
+    pub fn get_decoded_outputs(&self) -> Result<js_sys::Array, String> {
+        let function = self.program.get_function(&self.function_id).map_err(|e| e.to_string())?;
+        let output_types = function.output_types();
+        let array = js_sys::Array::new_with_length(0u32);
+        for (i, (output, output_type)) in self.response.outputs().iter().zip(output_types.iter()).enumerate() {
+            array.set(i as u32, output_to_js(output_type, output)?.into());
+        }
+        Ok(array)
+    }
+
     /// Returns the execution object if present, null if otherwise.
     ///
     /// @returns {Execution | undefined} The execution object if present, null if otherwise
@@ -103,10 +142,11 @@ impl ExecutionResponse {
     /// Returns the program keys if present
     #[wasm_bindgen(js_name = "getKeys")]
     pub fn get_keys(&mut self) -> Result<KeyPair, String> {
-        if let Some(proving_key) = self.proving_key.take() {
-            Ok(KeyPair::new(ProvingKey::from(proving_key), VerifyingKey::from(self.verifying_key.clone())))
-        } else {
-            Err("No proving key found".to_string())
+        match (self.proving_key.take(), self.verifying_key.clone()) {
+            (Some(proving_key), Some(verifying_key)) => {
+                Ok(KeyPair::new(ProvingKey::from(proving_key), VerifyingKey::from(verifying_key)))
+            }
+            _ => Err("No proving key found".to_string()),
         }
     }
 
@@ -120,12 +160,16 @@ impl ExecutionResponse {
         self.proving_key.take().map(ProvingKey::from)
     }
 
-    /// Returns the verifying_key associated with the program
+    /// Returns the verifying_key associated with the program. Responses of functions that were
+    /// only evaluated do not have a verifying key.
     ///
-    /// @returns {VerifyingKey} The verifying key
+    /// @returns {VerifyingKey | Error} The verifying key
     #[wasm_bindgen(js_name = "getVerifyingKey")]
-    pub fn get_verifying_key(&self) -> VerifyingKey {
-        VerifyingKey::from(self.verifying_key.clone())
+    pub fn get_verifying_key(&self) -> Result<VerifyingKey, String> {
+        self.verifying_key
+            .clone()
+            .map(VerifyingKey::from)
+            .ok_or("No verifying key found, the function was evaluated without synthesizing keys".to_string())
     }
 
     /// Returns the function identifier
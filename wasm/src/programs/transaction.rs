@@ -14,10 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::types::native::TransactionNative;
+use crate::types::native::{FieldNative, TransactionNative};
 
+use js_sys::Array;
 use std::str::FromStr;
-use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
 
 /// Webassembly Representation of an Aleo transaction
 ///
@@ -71,6 +72,25 @@ impl Transaction {
             TransactionNative::Fee(..) => "fee".to_string(),
         }
     }
+
+    /// Get the tags of all record inputs of the transitions in the transaction (including the fee
+    /// transition). A record whose tag is present in this list has been spent by the transaction.
+    ///
+    /// @returns {Array} Array of strings representing the record tags
+    #[wasm_bindgen(js_name = recordTags)]
+    pub fn record_tags(&self) -> Array {
+        self.0.tags().map(|tag| JsValue::from_str(&tag.to_string())).collect::<Array>()
+    }
+
+    /// Determine if a record tag is present among the record inputs of the transaction
+    ///
+    /// @param {string} tag String representation of a record tag
+    /// @returns {boolean | Error} True if the transaction spends the record with the given tag
+    #[wasm_bindgen(js_name = hasRecordTag)]
+    pub fn has_record_tag(&self, tag: &str) -> Result<bool, String> {
+        let tag = FieldNative::from_str(tag).map_err(|_| format!("{tag} is not a valid record tag"))?;
+        Ok(self.0.tags().any(|transaction_tag| *transaction_tag == tag))
+    }
 }
 
 impl From<Transaction> for TransactionNative {
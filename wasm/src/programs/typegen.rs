@@ -0,0 +1,332 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    programs::abi::{PlaintextTypeAbi, ProgramAbi, ValueTypeAbi},
+    types::native::ProgramNative,
+};
+
+use serde_json::{json, Map, Value};
+use std::fmt::Write;
+
+/// Runtime helpers emitted once into every generated module. They parse the textual Aleo
+/// representation of outputs and decode it according to the schemas emitted for the program.
+const RUNTIME: &str = r#"type AleoSchema =
+    | { kind: "literal"; type: string }
+    | { kind: "struct"; name: string }
+    | { kind: "array"; element: AleoSchema; length: number }
+    | { kind: "record"; record: string }
+    | { kind: "raw" };
+
+type AleoText = string | AleoText[] | { [member: string]: AleoText };
+
+function parseAleoText(text: string): AleoText {
+    const tokens = text.match(/"(?:[^"\\]|\\.)*"|[{}\[\],:]|[^\s{}\[\],:]+/g) ?? [];
+    let position = 0;
+    const next = (): AleoText => {
+        const token = tokens[position++];
+        if (token === "{") {
+            const members: { [member: string]: AleoText } = {};
+            while (tokens[position] !== "}") {
+                const name = tokens[position];
+                position += 2;
+                members[name] = next();
+                if (tokens[position] === ",") position++;
+            }
+            position++;
+            return members;
+        }
+        if (token === "[") {
+            const elements: AleoText[] = [];
+            while (tokens[position] !== "]") {
+                elements.push(next());
+                if (tokens[position] === ",") position++;
+            }
+            position++;
+            return elements;
+        }
+        return token.replace(/\.(constant|public|private)$/, "");
+    };
+    return next();
+}
+
+function decodeLiteral(text: string, type: string): unknown {
+    const value = text.endsWith(type) ? text.slice(0, -type.length) : text;
+    switch (type) {
+        case "boolean":
+            return value === "true";
+        case "u8": case "u16": case "u32": case "i8": case "i16": case "i32":
+            return Number(value);
+        case "u64": case "u128": case "i64": case "i128":
+            return BigInt(value);
+        default:
+            return text;
+    }
+}
+
+function decodeMembers(text: AleoText, members: { [member: string]: AleoSchema }): unknown {
+    const object = text as { [member: string]: AleoText };
+    return Object.fromEntries(
+        Object.entries(object).map(([name, value]) => [name, members[name] ? decodeText(value, members[name]) : value]),
+    );
+}
+
+function decodeText(text: AleoText, schema: AleoSchema): unknown {
+    switch (schema.kind) {
+        case "literal":
+            return decodeLiteral(text as string, schema.type);
+        case "struct":
+            return decodeMembers(text, STRUCTS[schema.name]);
+        case "array":
+            return (text as AleoText[]).map((element) => decodeText(element, schema.element));
+        case "record":
+            return decodeMembers(text, RECORDS[schema.record]);
+        case "raw":
+            return text;
+    }
+}
+
+function decodeOutputs(outputs: string[], schemas: AleoSchema[]): unknown[] {
+    if (outputs.length !== schemas.length) {
+        throw new Error(`Expected ${schemas.length} outputs but received ${outputs.length}`);
+    }
+    return outputs.map((output, index) =>
+        schemas[index].kind === "raw" ? output : decodeText(parseAleoText(output), schemas[index]),
+    );
+}
+"#;
+
+/// Generate a TypeScript module for a program. The module contains an interface for every struct
+/// and record of the program, a typed input tuple for every function, the types of the decoded
+/// outputs of every function and a decoder turning the string outputs of an execution into them.
+pub fn generate_typescript(program: &ProgramNative) -> String {
+    let abi = ProgramAbi::from_program(program);
+    let mut module = String::new();
+
+    writeln!(module, "// TypeScript bindings for the `{}` program (ABI version {}).", abi.program, abi.version)
+        .unwrap();
+    writeln!(module, "// This file is generated, do not edit it by hand.").unwrap();
+    writeln!(module).unwrap();
+    writeln!(module, "export const PROGRAM_ID = \"{}\";", abi.program).unwrap();
+    writeln!(module).unwrap();
+
+    // Emit an interface for every struct.
+    for struct_abi in abi.structs.iter() {
+        writeln!(module, "/** The `{}` struct */", struct_abi.name).unwrap();
+        writeln!(module, "export interface {} {{", pascal_case(&struct_abi.name)).unwrap();
+        for member in struct_abi.members.iter() {
+            writeln!(module, "    {}: {};", member.name, decoded_type(&member.r#type)).unwrap();
+        }
+        writeln!(module, "}}\n").unwrap();
+    }
+
+    // Emit an interface for every record.
+    for record in abi.records.iter() {
+        writeln!(module, "/** The `{}` record */", record.name).unwrap();
+        writeln!(module, "export interface {}Record {{", pascal_case(&record.name)).unwrap();
+        writeln!(module, "    owner: string;").unwrap();
+        for entry in record.entries.iter() {
+            writeln!(module, "    {}: {};", entry.name, decoded_type(&entry.r#type)).unwrap();
+        }
+        writeln!(module, "    _nonce: string;").unwrap();
+        writeln!(module, "}}\n").unwrap();
+    }
+
+    // Emit the input tuple, the decoded output tuple and the output decoder of every function.
+    for function in abi.functions.iter() {
+        let name = pascal_case(&function.name);
+        writeln!(module, "/** The inputs of the `{}` function */", function.name).unwrap();
+        writeln!(module, "export type {name}Inputs = [").unwrap();
+        for input in function.inputs.iter() {
+            writeln!(module, "    {}: {},", input.register, input_type(&input.r#type)).unwrap();
+        }
+        writeln!(module, "];\n").unwrap();
+
+        writeln!(module, "/** The decoded outputs of the `{}` function */", function.name).unwrap();
+        let outputs = function.outputs.iter().map(output_type).collect::<Vec<_>>();
+        writeln!(module, "export type {name}Outputs = [{}];\n", outputs.join(", ")).unwrap();
+
+        let schemas = function.outputs.iter().map(output_schema).collect::<Vec<_>>();
+        writeln!(module, "/** Decode the outputs of an execution of the `{}` function */", function.name).unwrap();
+        writeln!(module, "export function decode{name}Outputs(outputs: string[]): {name}Outputs {{").unwrap();
+        writeln!(module, "    return decodeOutputs(outputs, {}) as {name}Outputs;", Value::from(schemas)).unwrap();
+        writeln!(module, "}}\n").unwrap();
+    }
+
+    // Emit the schemas of the structs and records used by the decoders.
+    let structs = abi
+        .structs
+        .iter()
+        .map(|struct_abi| {
+            let members = struct_abi
+                .members
+                .iter()
+                .map(|member| (member.name.clone(), json!(member.r#type)))
+                .collect::<Map<_, _>>();
+            (struct_abi.name.clone(), Value::Object(members))
+        })
+        .collect::<Map<_, _>>();
+    let records = abi
+        .records
+        .iter()
+        .map(|record| {
+            let entries =
+                record.entries.iter().map(|entry| (entry.name.clone(), json!(entry.r#type))).collect::<Map<_, _>>();
+            (record.name.clone(), Value::Object(entries))
+        })
+        .collect::<Map<_, _>>();
+    writeln!(
+        module,
+        "const STRUCTS: {{ [name: string]: {{ [member: string]: AleoSchema }} }} = {};",
+        Value::Object(structs)
+    )
+    .unwrap();
+    writeln!(
+        module,
+        "const RECORDS: {{ [name: string]: {{ [member: string]: AleoSchema }} }} = {};\n",
+        Value::Object(records)
+    )
+    .unwrap();
+    module.push_str(RUNTIME);
+
+    module
+}
+
+// Convert a snake case Aleo identifier into a pascal case TypeScript type name.
+fn pascal_case(identifier: &str) -> String {
+    identifier
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut characters = part.chars();
+            characters
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + characters.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+// Get the TypeScript type a plaintext value is decoded into.
+fn decoded_type(plaintext_type: &PlaintextTypeAbi) -> String {
+    match plaintext_type {
+        PlaintextTypeAbi::Literal { r#type } => match r#type.as_str() {
+            "boolean" => "boolean".to_string(),
+            "u8" | "u16" | "u32" | "i8" | "i16" | "i32" => "number".to_string(),
+            "u64" | "u128" | "i64" | "i128" => "bigint".to_string(),
+            _ => "string".to_string(),
+        },
+        PlaintextTypeAbi::Struct { name } => pascal_case(name),
+        PlaintextTypeAbi::Array { element, .. } => format!("Array<{}>", decoded_type(element)),
+    }
+}
+
+// Get the TypeScript type of the string an input must be provided as.
+fn input_type(value_type: &ValueTypeAbi) -> String {
+    match value_type {
+        ValueTypeAbi::Constant { r#type } | ValueTypeAbi::Public { r#type } | ValueTypeAbi::Private { r#type } => {
+            match r#type {
+                PlaintextTypeAbi::Literal { r#type } => match r#type.as_str() {
+                    "address" => "`aleo1${string}`".to_string(),
+                    "boolean" => "\"true\" | \"false\"".to_string(),
+                    "signature" => "`sign1${string}`".to_string(),
+                    "string" => "string".to_string(),
+                    literal_type => format!("`${{bigint}}{literal_type}`"),
+                },
+                PlaintextTypeAbi::Struct { name } => format!("string /* {name} */"),
+                PlaintextTypeAbi::Array { .. } => "string /* array */".to_string(),
+            }
+        }
+        ValueTypeAbi::Record { record } => format!("string /* {record} record */"),
+        ValueTypeAbi::ExternalRecord { locator } => format!("string /* {locator} record */"),
+        ValueTypeAbi::Future { locator } => format!("string /* {locator} future */"),
+    }
+}
+
+// Get the TypeScript type an output is decoded into.
+fn output_type(value_type: &ValueTypeAbi) -> String {
+    match value_type {
+        ValueTypeAbi::Constant { r#type } | ValueTypeAbi::Public { r#type } | ValueTypeAbi::Private { r#type } => {
+            decoded_type(r#type)
+        }
+        ValueTypeAbi::Record { record } => format!("{}Record", pascal_case(record)),
+        ValueTypeAbi::ExternalRecord { .. } | ValueTypeAbi::Future { .. } => "string".to_string(),
+    }
+}
+
+// Get the schema the runtime decoder uses for an output.
+fn output_schema(value_type: &ValueTypeAbi) -> Value {
+    match value_type {
+        ValueTypeAbi::Constant { r#type } | ValueTypeAbi::Public { r#type } | ValueTypeAbi::Private { r#type } => {
+            json!(r#type)
+        }
+        ValueTypeAbi::Record { record } => json!({ "kind": "record", "record": record }),
+        ValueTypeAbi::ExternalRecord { .. } | ValueTypeAbi::Future { .. } => json!({ "kind": "raw" }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::str::FromStr;
+    use wasm_bindgen_test::*;
+
+    const TOKEN_PROGRAM: &str = r#"program token_types.aleo;
+
+struct token_metadata:
+    token_id as u32;
+    version as u64;
+
+record Token:
+    owner as address.private;
+    amount as u64.private;
+    metadata as token_metadata.private;
+
+function mint:
+    input r0 as address.private;
+    input r1 as u64.public;
+    input r2 as token_metadata.private;
+    cast r0 r1 r2 into r3 as Token.record;
+    output r3 as Token.record;
+    output r2 as token_metadata.private;
+"#;
+
+    #[wasm_bindgen_test]
+    fn test_pascal_case() {
+        assert_eq!(pascal_case("token_metadata"), "TokenMetadata");
+        assert_eq!(pascal_case("transfer_public_to_private"), "TransferPublicToPrivate");
+        assert_eq!(pascal_case("Token"), "Token");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_generate_typescript() {
+        let program = ProgramNative::from_str(TOKEN_PROGRAM).unwrap();
+        let module = generate_typescript(&program);
+
+        assert!(module.contains("export const PROGRAM_ID = \"token_types.aleo\";"));
+        assert!(module.contains("export interface TokenMetadata {\n    token_id: number;\n    version: bigint;\n}"));
+        assert!(module.contains(
+            "export interface TokenRecord {\n    owner: string;\n    amount: bigint;\n    metadata: TokenMetadata;\n    _nonce: string;\n}"
+        ));
+        assert!(module.contains(
+            "export type MintInputs = [\n    r0: `aleo1${string}`,\n    r1: `${bigint}u64`,\n    r2: string /* token_metadata */,\n];"
+        ));
+        assert!(module.contains("export type MintOutputs = [TokenRecord, TokenMetadata];"));
+        assert!(module.contains("export function decodeMintOutputs(outputs: string[]): MintOutputs {"));
+        assert!(module.contains(r#"{"kind":"record","record":"Token"}"#));
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::types::native::{
+    AddressNative,
+    CurrentNetwork,
+    Entry,
+    EntryType,
+    IdentifierNative,
+    PlaintextNative,
+    PlaintextType,
+    ProcessNative,
+    ProgramNative,
+    RecordPlaintextNative,
+    ValueType,
+};
+
+use js_sys::{Object, Reflect};
+use std::{fmt, str::FromStr};
+use wasm_bindgen::JsValue;
+
+/// A problem found with a single input of a function call
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputDiagnostic {
+    /// The position of the input in the list of inputs
+    pub index: usize,
+    /// The register the function reads the input into
+    pub register: String,
+    /// The type the function declares for the input
+    pub expected: String,
+    /// A description of the problem
+    pub message: String,
+}
+
+impl InputDiagnostic {
+    /// Convert the diagnostic into a javascript object
+    pub fn to_object(&self) -> Result<Object, String> {
+        let object = Object::new();
+        Reflect::set(&object, &"index".into(), &JsValue::from(self.index as u32))
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"register".into(), &self.register.clone().into())
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"expected".into(), &self.expected.clone().into())
+            .map_err(|_| "Failed to set property")?;
+        Reflect::set(&object, &"message".into(), &self.message.clone().into()).map_err(|_| "Failed to set property")?;
+        Ok(object)
+    }
+}
+
+impl fmt::Display for InputDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "input {} ({}, expected {}): {}", self.index, self.register, self.expected, self.message)
+    }
+}
+
+/// Check the inputs of a function call against the types declared by the function without
+/// executing it. Literals are parsed, structs and arrays are checked against their definitions,
+/// records are checked against the record definition and, if a caller is provided, against the
+/// caller's address. External records are resolved through the programs loaded in the process.
+///
+/// A diagnostic is returned for every input that does not match, an empty list means the inputs
+/// are well formed.
+pub fn validate_inputs(
+    process: &ProcessNative,
+    program: &ProgramNative,
+    function_id: &IdentifierNative,
+    inputs: &[String],
+    caller: Option<&AddressNative>,
+) -> Result<Vec<InputDiagnostic>, String> {
+    let function = program.get_function(function_id).map_err(|e| e.to_string())?;
+    let mut diagnostics = Vec::new();
+
+    for (index, input) in function.inputs().iter().enumerate() {
+        let register = input.register().to_string();
+        let expected = input.value_type().to_string();
+        let result = match inputs.get(index) {
+            Some(value) => validate_value(process, program, input.value_type(), value, caller),
+            None => Err("missing input".to_string()),
+        };
+        if let Err(message) = result {
+            diagnostics.push(InputDiagnostic { index, register, expected, message });
+        }
+    }
+
+    for index in function.inputs().len()..inputs.len() {
+        diagnostics.push(InputDiagnostic {
+            index,
+            register: "none".to_string(),
+            expected: "none".to_string(),
+            message: format!("unexpected input, {function_id} takes {} inputs", function.inputs().len()),
+        });
+    }
+
+    Ok(diagnostics)
+}
+
+// Check a single input against its declared value type.
+fn validate_value(
+    process: &ProcessNative,
+    program: &ProgramNative,
+    value_type: &ValueType<CurrentNetwork>,
+    value: &str,
+    caller: Option<&AddressNative>,
+) -> Result<(), String> {
+    match value_type {
+        ValueType::Constant(plaintext_type)
+        | ValueType::Public(plaintext_type)
+        | ValueType::Private(plaintext_type) => {
+            let plaintext =
+                PlaintextNative::from_str(value).map_err(|_| format!("'{value}' is not a valid plaintext"))?;
+            validate_plaintext(program, &plaintext, plaintext_type)
+        }
+        ValueType::Record(record_name) => validate_record(program, record_name, value, caller),
+        ValueType::ExternalRecord(locator) => {
+            if !program.imports().contains_key(locator.program_id()) {
+                return Err(format!("{} is not imported by {}", locator.program_id(), program.id()));
+            }
+            let external_program = process
+                .get_program(locator.program_id())
+                .map_err(|_| format!("{} has not been loaded, provide it as an import", locator.program_id()))?;
+            validate_record(external_program, locator.resource(), value, caller)
+        }
+        ValueType::Future(_) => Err("futures cannot be provided as inputs".to_string()),
+    }
+}
+
+// Check that a record input matches the record definition and is owned by the caller.
+fn validate_record(
+    program: &ProgramNative,
+    record_name: &IdentifierNative,
+    value: &str,
+    caller: Option<&AddressNative>,
+) -> Result<(), String> {
+    let record_type = program.get_record(record_name).map_err(|e| e.to_string())?;
+    let record =
+        RecordPlaintextNative::from_str(value).map_err(|_| format!("'{value}' is not a valid record plaintext"))?;
+
+    if record.owner().is_public() != record_type.owner().is_public() {
+        let visibility = if record_type.owner().is_public() { "public" } else { "private" };
+        return Err(format!("the owner of a {record_name} record must be {visibility}"));
+    }
+    if let Some(caller) = caller {
+        if **record.owner() != *caller {
+            return Err(format!("the record is owned by {}, not by the caller {caller}", **record.owner()));
+        }
+    }
+
+    if record.data().len() != record_type.entries().len() {
+        return Err(format!(
+            "a {record_name} record has {} entries, found {}",
+            record_type.entries().len(),
+            record.data().len()
+        ));
+    }
+    for (entry_name, entry_type) in record_type.entries().iter() {
+        let entry = record
+            .data()
+            .get(entry_name)
+            .ok_or_else(|| format!("the record is missing the '{entry_name}' entry of a {record_name} record"))?;
+        let (plaintext, plaintext_type) = match (entry, entry_type) {
+            (Entry::Constant(plaintext), EntryType::Constant(plaintext_type))
+            | (Entry::Public(plaintext), EntryType::Public(plaintext_type))
+            | (Entry::Private(plaintext), EntryType::Private(plaintext_type)) => (plaintext, plaintext_type),
+            _ => return Err(format!("the '{entry_name}' entry must be {entry_type}")),
+        };
+        validate_plaintext(program, plaintext, plaintext_type).map_err(|e| format!("entry '{entry_name}': {e}"))?;
+    }
+    Ok(())
+}
+
+// Check that a plaintext value matches a plaintext type, recursing into structs and arrays.
+fn validate_plaintext(
+    program: &ProgramNative,
+    plaintext: &PlaintextNative,
+    plaintext_type: &PlaintextType<CurrentNetwork>,
+) -> Result<(), String> {
+    match (plaintext, plaintext_type) {
+        (PlaintextNative::Literal(literal, _), PlaintextType::Literal(literal_type)) => {
+            if literal.to_type() != *literal_type {
+                return Err(format!("expected a {literal_type} literal, found {literal}"));
+            }
+            Ok(())
+        }
+        (PlaintextNative::Struct(members, _), PlaintextType::Struct(struct_name)) => {
+            let struct_type = program.get_struct(struct_name).map_err(|e| e.to_string())?;
+            if members.len() != struct_type.members().len() {
+                return Err(format!(
+                    "the {struct_name} struct has {} members, found {}",
+                    struct_type.members().len(),
+                    members.len()
+                ));
+            }
+            for (member_name, member_type) in struct_type.members().iter() {
+                let member = members
+                    .get(member_name)
+                    .ok_or_else(|| format!("the '{member_name}' member of the {struct_name} struct is missing"))?;
+                validate_plaintext(program, member, member_type).map_err(|e| format!("member '{member_name}': {e}"))?;
+            }
+            Ok(())
+        }
+        (PlaintextNative::Array(elements, _), PlaintextType::Array(array_type)) => {
+            if elements.len() != **array_type.length() as usize {
+                return Err(format!(
+                    "expected an array of {} elements, found {}",
+                    **array_type.length(),
+                    elements.len()
+                ));
+            }
+            for (index, element) in elements.iter().enumerate() {
+                validate_plaintext(program, element, array_type.next_element_type())
+                    .map_err(|e| format!("element {index}: {e}"))?;
+            }
+            Ok(())
+        }
+        (_, plaintext_type) => Err(format!("expected a value of type {plaintext_type}, found {plaintext}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::native::PrivateKeyNative;
+
+    use wasm_bindgen_test::*;
+
+    const TOKEN_PROGRAM: &str = r#"program token_checks.aleo;
+
+struct metadata:
+    id as u32;
+    tags as [u8; 2u32];
+
+record Token:
+    owner as address.private;
+    amount as u64.private;
+
+function send:
+    input r0 as Token.record;
+    input r1 as address.private;
+    input r2 as metadata.public;
+    cast r1 r0.amount into r3 as Token.record;
+    output r3 as Token.record;
+"#;
+
+    const OWNER: &str = "aleo1cfr9mr3zum3h8tadfzan6dvvjh3cj3s0v8ha22zp88cpfn0n3gxq4m0dd0";
+
+    fn token(owner: &str) -> String {
+        format!("{{ owner: {owner}.private, amount: 100u64.private, _nonce: 0group.public }}")
+    }
+
+    #[wasm_bindgen_test]
+    fn test_validate_inputs() {
+        let process = ProcessNative::load_web().unwrap();
+        let program = ProgramNative::from_str(TOKEN_PROGRAM).unwrap();
+        let function = IdentifierNative::from_str("send").unwrap();
+        let owner = AddressNative::from_str(OWNER).unwrap();
+
+        // Well formed inputs produce no diagnostics
+        let inputs = vec![token(OWNER), OWNER.to_string(), "{ id: 1u32, tags: [1u8, 2u8] }".to_string()];
+        assert!(validate_inputs(&process, &program, &function, &inputs, Some(&owner)).unwrap().is_empty());
+
+        // Every malformed input is reported individually
+        let inputs = vec![token(OWNER), "5u32".to_string(), "{ id: 1u32, tags: [1u8] }".to_string()];
+        let diagnostics = validate_inputs(&process, &program, &function, &inputs, Some(&owner)).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].index, 1);
+        assert_eq!(diagnostics[0].register, "r1");
+        assert_eq!(diagnostics[1].index, 2);
+        assert!(diagnostics[1].message.contains("tags"));
+
+        // Records owned by another account are rejected
+        let other = AddressNative::try_from(PrivateKeyNative::new(&mut rand::thread_rng()).unwrap()).unwrap();
+        let inputs = vec![token(OWNER), OWNER.to_string(), "{ id: 1u32, tags: [1u8, 2u8] }".to_string()];
+        let diagnostics = validate_inputs(&process, &program, &function, &inputs, Some(&other)).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].register, "r0");
+
+        // Missing and extra inputs are reported
+        let diagnostics = validate_inputs(&process, &program, &function, &[token(OWNER)], None).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[1].message, "missing input");
+        let mut inputs = vec![token(OWNER), OWNER.to_string(), "{ id: 1u32, tags: [1u8, 2u8] }".to_string()];
+        inputs.push("1u8".to_string());
+        let diagnostics = validate_inputs(&process, &program, &function, &inputs, None).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].index, 3);
+    }
+}
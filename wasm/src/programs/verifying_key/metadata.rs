@@ -31,6 +31,12 @@ pub struct Metadata {
     #[wasm_bindgen(getter_with_clone)]
     pub verifier: String,
 
+    #[wasm_bindgen(getter_with_clone, js_name = proverChecksum)]
+    pub prover_checksum: String,
+
+    #[wasm_bindgen(skip)]
+    pub prover_size: Option<u64>,
+
     #[wasm_bindgen(getter_with_clone, js_name = verifyingKey)]
     pub verifying_key: String,
 }
@@ -39,21 +45,34 @@ impl Metadata {
     const BASE_URL: &'static str = "https://parameters.aleo.org/testnet/";
 
     fn new(name: &str, verifying_key: &str, locator: &str, prover: &'static str, verifier: &'static str) -> Self {
-        fn url(function_name: &str, kind: &str, proving_key_metadata: &'static str) -> String {
-            let metadata: serde_json::Value =
-                serde_json::from_str(proving_key_metadata).expect("Metadata was not well-formatted");
-            let checksum = metadata["prover_checksum"].as_str().expect("Failed to parse checksum").to_string();
+        fn parse(key_metadata: &'static str) -> serde_json::Value {
+            serde_json::from_str(key_metadata).expect("Metadata was not well-formatted")
+        }
+
+        fn checksum(key_metadata: &'static str, field: &str) -> String {
+            parse(key_metadata)[field].as_str().expect("Failed to parse checksum").to_string()
+        }
+
+        fn url(function_name: &str, kind: &str, checksum: &str) -> String {
             format!("{}.{}.{}", function_name, kind, checksum.get(0..7).unwrap())
         }
 
+        let prover_checksum = checksum(prover, "prover_checksum");
         Self {
             name: name.to_string(),
             locator: locator.to_string(),
-            prover: format!("{}{}", Self::BASE_URL, url(name, "prover", prover)),
-            verifier: url(name, "verifier", verifier),
+            prover: format!("{}{}", Self::BASE_URL, url(name, "prover", &prover_checksum)),
+            verifier: url(name, "verifier", &checksum(verifier, "prover_checksum")),
             verifying_key: verifying_key.to_string(),
+            prover_checksum,
+            prover_size: parse(prover)["prover_size"].as_u64(),
         }
     }
+
+    /// Get the name of the prover file, which is the same on the parameter server and in mirrors of it
+    pub fn prover_file_name(&self) -> &str {
+        self.prover.rsplit('/').next().unwrap_or(&self.prover)
+    }
 }
 
 #[wasm_bindgen]
@@ -63,6 +82,16 @@ impl Metadata {
         Self::BASE_URL.to_string()
     }
 
+    /// Get the metadata of a function whose keys are published on the parameter server
+    ///
+    /// @param {string} function The name of the function, such as "transfer_public", or its
+    /// locator, such as "credits.aleo/transfer_public"
+    /// @returns {Metadata | Error} The metadata of the function
+    #[wasm_bindgen(js_name = fromFunction)]
+    pub fn from_function(function: &str) -> Result<Metadata, String> {
+        Ok(crate::programs::published_function(function)?.metadata)
+    }
+
     #[wasm_bindgen]
     pub fn bond_public() -> Metadata {
         Metadata::new(
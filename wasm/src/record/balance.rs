@@ -0,0 +1,182 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the Aleo SDK library.
+
+// The Aleo SDK library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Aleo SDK library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::types::native::{
+    Entry,
+    IdentifierNative,
+    LiteralNative,
+    LiteralType,
+    PlaintextNative,
+    RecordPlaintextNative,
+};
+
+use js_sys::Array;
+use std::str::FromStr;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+/// Well-known token record layouts in the form (record locator, balance field, integer type)
+const TOKEN_LAYOUTS: [(&str, &str, &str); 2] =
+    [("credits.aleo/credits", "microcredits", "u64"), ("token_registry.aleo/Token", "amount", "u128")];
+
+/// Description of where the balance of a token record is stored. A balance extractor names the
+/// record field holding the balance and the integer type of that field. The field is read
+/// regardless of whether it is a constant, public or private entry of the record.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BalanceExtractor {
+    field: IdentifierNative,
+    integer_type: LiteralType,
+}
+
+#[wasm_bindgen]
+impl BalanceExtractor {
+    /// Create a balance extractor for a record field of a given integer type
+    ///
+    /// @param {string} field Name of the record field holding the balance
+    /// @param {string} integer_type Integer type of the field (i.e. "u64" or "u128")
+    /// @returns {BalanceExtractor | Error}
+    #[wasm_bindgen(constructor)]
+    pub fn new(field: &str, integer_type: &str) -> Result<BalanceExtractor, String> {
+        let field = IdentifierNative::from_str(field).map_err(|_| format!("{field} is an invalid identifier"))?;
+        let integer_type =
+            LiteralType::from_str(integer_type).map_err(|_| format!("{integer_type} is an invalid literal type"))?;
+        match integer_type {
+            LiteralType::U8
+            | LiteralType::U16
+            | LiteralType::U32
+            | LiteralType::U64
+            | LiteralType::U128
+            | LiteralType::I8
+            | LiteralType::I16
+            | LiteralType::I32
+            | LiteralType::I64
+            | LiteralType::I128 => Ok(Self { field, integer_type }),
+            _ => Err(format!("{integer_type} is not an integer type")),
+        }
+    }
+
+    /// Get the balance extractor for credits.aleo records
+    ///
+    /// @returns {BalanceExtractor} Balance extractor reading the microcredits field
+    pub fn credits() -> BalanceExtractor {
+        Self::from_registry("credits.aleo/credits").unwrap()
+    }
+
+    /// Get the balance extractor of a well-known token record layout
+    ///
+    /// @param {string} locator Locator of the record in the form "program.aleo/RecordName"
+    /// @returns {BalanceExtractor | Error}
+    #[wasm_bindgen(js_name = "fromRegistry")]
+    pub fn from_registry(locator: &str) -> Result<BalanceExtractor, String> {
+        let (_, field, integer_type) = TOKEN_LAYOUTS
+            .iter()
+            .find(|(record, _, _)| *record == locator)
+            .ok_or_else(|| format!("No known token record layout for {locator}"))?;
+        Self::new(field, integer_type)
+    }
+
+    /// Get the locators of all well-known token record layouts
+    ///
+    /// @returns {Array} Array of record locators with a known layout
+    #[wasm_bindgen(js_name = "registeredLayouts")]
+    pub fn registered_layouts() -> Array {
+        TOKEN_LAYOUTS.iter().map(|(record, _, _)| JsValue::from_str(record)).collect::<Array>()
+    }
+
+    /// Get the name of the record field holding the balance
+    ///
+    /// @returns {string} Name of the balance field
+    pub fn field(&self) -> String {
+        self.field.to_string()
+    }
+
+    /// Get the integer type of the balance field
+    ///
+    /// @returns {string} Integer type of the balance field
+    #[wasm_bindgen(js_name = "integerType")]
+    pub fn integer_type(&self) -> String {
+        self.integer_type.to_string()
+    }
+}
+
+impl BalanceExtractor {
+    /// Extract the balance from a record. Negative balances of signed integer types are rejected.
+    pub fn extract(&self, record: &RecordPlaintextNative) -> Result<u128, String> {
+        let missing_field = || format!("The record provided does not contain a {} field", self.field);
+        let plaintext = match record.find(&[self.field]).map_err(|_| missing_field())? {
+            Entry::Constant(plaintext) | Entry::Public(plaintext) | Entry::Private(plaintext) => plaintext,
+        };
+        let literal = match plaintext {
+            PlaintextNative::Literal(literal, _) if literal.to_type() == self.integer_type => literal,
+            _ => return Err(format!("The {} field of the record is not of type {}", self.field, self.integer_type)),
+        };
+        let negative = || format!("The {} field of the record holds a negative balance", self.field);
+        match literal {
+            LiteralNative::U8(amount) => Ok(u128::from(*amount)),
+            LiteralNative::U16(amount) => Ok(u128::from(*amount)),
+            LiteralNative::U32(amount) => Ok(u128::from(*amount)),
+            LiteralNative::U64(amount) => Ok(u128::from(*amount)),
+            LiteralNative::U128(amount) => Ok(*amount),
+            LiteralNative::I8(amount) => u128::try_from(*amount).map_err(|_| negative()),
+            LiteralNative::I16(amount) => u128::try_from(*amount).map_err(|_| negative()),
+            LiteralNative::I32(amount) => u128::try_from(*amount).map_err(|_| negative()),
+            LiteralNative::I64(amount) => u128::try_from(*amount).map_err(|_| negative()),
+            LiteralNative::I128(amount) => u128::try_from(*amount).map_err(|_| negative()),
+            _ => Err(format!("The {} field of the record is not an integer", self.field)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    const CREDITS_RECORD: &str = r"{
+  owner: aleo1j7qxyunfldj2lp8hsvy7mw5k8zaqgjfyr72x2gh3x4ewgae8v5gscf5jh3.private,
+  microcredits: 1500000000000000u64.private,
+  _nonce: 3077450429259593211617823051143573281856129402760267155982965992208217472983group.public
+}";
+
+    const TOKEN_RECORD: &str = r"{
+  owner: aleo1j7qxyunfldj2lp8hsvy7mw5k8zaqgjfyr72x2gh3x4ewgae8v5gscf5jh3.private,
+  amount: 340282366920938463463374607431768211455u128.public,
+  _nonce: 3077450429259593211617823051143573281856129402760267155982965992208217472983group.public
+}";
+
+    #[wasm_bindgen_test]
+    fn test_extract_credits() {
+        let record = RecordPlaintextNative::from_str(CREDITS_RECORD).unwrap();
+        assert_eq!(BalanceExtractor::credits().extract(&record).unwrap(), 1500000000000000);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_extract_public_token_balance() {
+        let record = RecordPlaintextNative::from_str(TOKEN_RECORD).unwrap();
+        let extractor = BalanceExtractor::from_registry("token_registry.aleo/Token").unwrap();
+        assert_eq!(extractor.extract(&record).unwrap(), u128::MAX);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_extract_mismatched_layout_fails() {
+        let record = RecordPlaintextNative::from_str(TOKEN_RECORD).unwrap();
+        assert!(BalanceExtractor::credits().extract(&record).is_err());
+        assert!(BalanceExtractor::new("amount", "u64").unwrap().extract(&record).is_err());
+        assert!(BalanceExtractor::new("amount", "field").is_err());
+        assert!(BalanceExtractor::from_registry("unknown.aleo/Token").is_err());
+    }
+}
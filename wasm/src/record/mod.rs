@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
+pub mod balance;
+pub use balance::*;
+
 pub mod record_ciphertext;
 pub use record_ciphertext::*;
 
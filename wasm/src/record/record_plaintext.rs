@@ -14,7 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{account::PrivateKey, types::Field, Credits};
+use crate::{
+    account::{GraphKey, PrivateKey},
+    record::BalanceExtractor,
+    types::Field,
+    Credits,
+    Transaction,
+};
 
 use crate::types::native::{IdentifierNative, ProgramIDNative, RecordPlaintextNative};
 use std::{ops::Deref, str::FromStr};
@@ -65,6 +71,15 @@ impl RecordPlaintext {
         self.0.microcredits().unwrap_or(0)
     }
 
+    /// Returns the token balance of the record as described by a balance extractor. This allows
+    /// records of token programs other than credits.aleo to be inspected in the same way
+    ///
+    /// @param {BalanceExtractor} extractor Description of the record field holding the balance
+    /// @returns {BigInt | Error} Balance of the record
+    pub fn balance(&self, extractor: &BalanceExtractor) -> Result<js_sys::BigInt, String> {
+        Ok(js_sys::BigInt::from(self.0.balance(extractor)?))
+    }
+
     /// Returns the nonce of the record. This can be used to uniquely identify a record.
     ///
     /// @returns {string} Nonce of the record
@@ -92,6 +107,43 @@ impl RecordPlaintext {
             .map_err(|_| "Serial number derivation failed".to_string())?;
         Ok(serial_number.to_string())
     }
+
+    /// Get the tag of a record. Unlike the serial number, the tag can be computed with only the
+    /// graph key of the record owner, so holders of a view key can use it to determine whether a
+    /// record has been spent by checking it against the input tags of on-chain transitions
+    ///
+    /// @param {GraphKey} graph_key Graph key of the account that owns the record
+    /// @param {string} program_id Program ID of the program that the record is associated with
+    /// @param {string} record_name Name of the record
+    /// @returns {string | Error} Tag of the record
+    #[wasm_bindgen(js_name = tagString)]
+    pub fn tag_string(&self, graph_key: &GraphKey, program_id: &str, record_name: &str) -> Result<String, String> {
+        let commitment = self.commitment(program_id, record_name)?;
+
+        let tag = RecordPlaintextNative::tag(graph_key.clone().into(), commitment.into())
+            .map_err(|_| "Tag derivation failed".to_string())?;
+        Ok(tag.to_string())
+    }
+
+    /// Determine whether the record is spent by a transaction by checking the record tag against
+    /// the tags of the record inputs of the transaction's transitions
+    ///
+    /// @param {GraphKey} graph_key Graph key of the account that owns the record
+    /// @param {string} program_id Program ID of the program that the record is associated with
+    /// @param {string} record_name Name of the record
+    /// @param {Transaction} transaction Transaction to check the record tag against
+    /// @returns {boolean | Error} True if the transaction spends the record, false otherwise
+    #[wasm_bindgen(js_name = isSpentIn)]
+    pub fn is_spent_in(
+        &self,
+        graph_key: &GraphKey,
+        program_id: &str,
+        record_name: &str,
+        transaction: &Transaction,
+    ) -> Result<bool, String> {
+        let tag = self.tag_string(graph_key, program_id, record_name)?;
+        transaction.has_record_tag(&tag)
+    }
 }
 
 impl From<RecordPlaintextNative> for RecordPlaintext {
@@ -140,6 +192,13 @@ mod tests {
         assert_eq!(record.microcredits(), 1500000000000000);
     }
 
+    #[wasm_bindgen_test]
+    fn test_balance_from_string() {
+        let record = RecordPlaintext::from_string(RECORD).unwrap();
+        let balance = record.balance(&BalanceExtractor::credits()).unwrap();
+        assert_eq!(balance, js_sys::BigInt::from(1500000000000000u64));
+    }
+
     #[wasm_bindgen_test]
     fn test_serial_number() {
         let pk = PrivateKey::from_string("APrivateKey1zkpDeRpuKmEtLNPdv57aFruPepeH1aGvTkEjBo8bqTzNUhE").unwrap();
@@ -180,6 +239,14 @@ mod tests {
         assert!(record.serial_number_string(&pk, program_id, record_name).is_err());
     }
 
+    #[wasm_bindgen_test]
+    fn test_tag_invalid_record_name_returns_err_string() {
+        let pk = PrivateKey::from_string("APrivateKey1zkpDeRpuKmEtLNPdv57aFruPepeH1aGvTkEjBo8bqTzNUhE").unwrap();
+        let record = RecordPlaintext::from_string(RECORD).unwrap();
+        let graph_key = GraphKey::from_private_key(&pk).unwrap();
+        assert!(record.tag_string(&graph_key, "token.aleo", "not a real record name").is_err());
+    }
+
     #[wasm_bindgen_test]
     fn test_bad_inputs_to_from_string() {
         let invalid_bech32 = "{ owner: aleo2d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, microcredits: 99u64.public, _nonce: 0group.public }";
@@ -16,29 +16,54 @@
 
 pub use snarkvm_circuit_network::AleoTestnetV0;
 pub use snarkvm_console::{
-    account::{Address, PrivateKey, Signature, ViewKey},
+    account::{Address, GraphKey, PrivateKey, Signature, ViewKey},
     network::{Network, TestnetV0},
     program::{
+        Argument,
         Ciphertext,
         Entry,
         EntryType,
+        FinalizeType,
+        Future,
         Identifier,
         Literal,
+        LiteralType,
         Plaintext,
         PlaintextType,
         ProgramID,
         ProgramOwner,
         Record,
+        RegisterType,
         Response,
+        Value,
         ValueType,
     },
     types::Field,
 };
-pub use snarkvm_ledger_block::{Execution, Transaction};
+pub use snarkvm_ledger_block::{
+    Block,
+    ConfirmedTransaction,
+    Deployment,
+    Execution,
+    Fee,
+    Header,
+    Metadata,
+    Ratifications,
+    Rejected,
+    Solutions,
+    Transaction,
+    Transactions,
+    FINALIZE_OPERATIONS_DEPTH,
+};
 pub use snarkvm_ledger_query::Query;
-pub use snarkvm_ledger_store::helpers::memory::BlockMemory;
+pub use snarkvm_ledger_store::{
+    helpers::memory::{BlockMemory, FinalizeMemory},
+    BlockStore,
+    FinalizeStore,
+};
 pub use snarkvm_synthesizer::{
     process::{cost_in_microcredits, deployment_cost},
+    program::{CallOperator, Command, FinalizeGlobalState, Instruction},
     snark::{ProvingKey, VerifyingKey},
     Process,
     Program,
@@ -46,11 +71,12 @@ pub use snarkvm_synthesizer::{
 pub use snarkvm_wasm::{
     console::network::Environment,
     fields::PrimeField,
-    utilities::{FromBytes, ToBytes, Uniform},
+    utilities::{FromBytes, ToBits, ToBytes, Uniform},
 };
 
 // Account types
 pub type AddressNative = Address<CurrentNetwork>;
+pub type GraphKeyNative = GraphKey<CurrentNetwork>;
 pub type PrivateKeyNative = PrivateKey<CurrentNetwork>;
 pub type SignatureNative = Signature<CurrentNetwork>;
 pub type ViewKeyNative = ViewKey<CurrentNetwork>;
@@ -67,10 +93,17 @@ pub type CiphertextNative = Ciphertext<CurrentNetwork>;
 pub type PlaintextNative = Plaintext<CurrentNetwork>;
 pub type RecordCiphertextNative = Record<CurrentNetwork, CiphertextNative>;
 pub type RecordPlaintextNative = Record<CurrentNetwork, PlaintextNative>;
+pub type ValueNative = Value<CurrentNetwork>;
 
 // Program types
 type CurrentBlockMemory = BlockMemory<CurrentNetwork>;
+pub type BlockNative = Block<CurrentNetwork>;
+pub type BlockStoreNative = BlockStore<CurrentNetwork, CurrentBlockMemory>;
+pub type DeploymentNative = Deployment<CurrentNetwork>;
 pub type ExecutionNative = Execution<CurrentNetwork>;
+pub type FeeNative = Fee<CurrentNetwork>;
+pub type FinalizeStoreNative = FinalizeStore<CurrentNetwork, FinalizeMemory<CurrentNetwork>>;
+pub type FutureNative = Future<CurrentNetwork>;
 pub type IdentifierNative = Identifier<CurrentNetwork>;
 pub type LiteralNative = Literal<CurrentNetwork>;
 pub type ProcessNative = Process<CurrentNetwork>;
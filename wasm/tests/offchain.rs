@@ -14,9 +14,25 @@
 // You should have received a copy of the GNU General Public License
 // along with the Aleo SDK library. If not, see <https://www.gnu.org/licenses/>.
 
-use aleo_wasm::{PrivateKey, Program, ProgramManager, RecordPlaintext};
-use js_sys::{Array, Object, Reflect};
-use wasm_bindgen::JsValue;
+use aleo_wasm::{
+    Devnet,
+    Execution,
+    GraphKey,
+    JsKeyStorage,
+    KeyStore,
+    MappingStore,
+    OfflineQuery,
+    PrivateKey,
+    Program,
+    ProgramKeys,
+    ProgramManager,
+    RecordCiphertext,
+    RecordPlaintext,
+    Transaction,
+};
+use js_sys::{Array, BigInt, Function, Map, Object, Reflect, JSON};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen_test::*;
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -100,6 +116,32 @@ function add_and_double:
     output r3 as u32.private;
 "#;
 
+pub const TOKEN_PROGRAM: &str = r#"program devnet_token.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+mapping supply:
+    key as boolean.public;
+    value as u64.public;
+
+function mint:
+    input r0 as u64.public;
+    cast self.caller r0 into r1 as token.record;
+    async mint r0 into r2;
+    output r1 as token.record;
+    output r2 as devnet_token.aleo/mint.future;
+
+finalize mint:
+    input r0 as u64.public;
+    lte r0 100u64 into r1;
+    assert.eq r1 true;
+    get.or_use supply[true] 0u64 into r2;
+    add r2 r0 into r3;
+    set r3 into supply[true];
+"#;
+
 const RECORD: &str = "{  owner: aleo184vuwr5u7u0ha5f5k44067dd2uaqewxx6pe5ltha5pv99wvhfqxqv339h4.private,  microcredits: 2000000u64.private,  _nonce: 4106205762862305308495708971985748592380064201230396559307556388725936304984group.public}";
 
 #[wasm_bindgen_test]
@@ -156,7 +198,7 @@ async fn test_fee_validation() {
         &Program::get_credits_program().to_string(),
         "split",
         inputs,
-        100.0,
+        JsValue::from_str("100"),
         Some(fee_record.clone()),
         Some("https://api.explorer.aleo.org/v1".to_string()),
         None,
@@ -165,6 +207,8 @@ async fn test_fee_validation() {
         None,
         None,
         None,
+        JsValue::UNDEFINED,
+        None,
     )
     .await;
     assert!(execution.is_err());
@@ -173,13 +217,15 @@ async fn test_fee_validation() {
     let deployment = ProgramManager::deploy(
         &private_key,
         &Program::get_credits_program().to_string(),
-        100.0,
+        JsValue::from_str("100"),
         Some(fee_record.clone()),
         Some("https://api.explorer.aleo.org/v1".to_string()),
         None,
         None,
         None,
         None,
+        JsValue::UNDEFINED,
+        None,
     )
     .await;
     assert!(deployment.is_err());
@@ -187,11 +233,11 @@ async fn test_fee_validation() {
     // Ensure transfer fails when fee amount or amount is greater than the balance available in the record
     let transfer = ProgramManager::transfer(
         &private_key,
-        100.00,
+        JsValue::from_str("100"),
         "aleo184vuwr5u7u0ha5f5k44067dd2uaqewxx6pe5ltha5pv99wvhfqxqv339h4",
         "private",
         Some(fee_record.clone()),
-        0.9,
+        JsValue::from(0.9),
         Some(fee_record.clone()),
         Some("https://api.explorer.aleo.org/v1".to_string()),
         None,
@@ -199,17 +245,18 @@ async fn test_fee_validation() {
         None,
         None,
         None,
+        JsValue::UNDEFINED,
     )
     .await;
     assert!(transfer.is_err());
 
     let transfer = ProgramManager::transfer(
         &private_key,
-        0.5,
+        JsValue::from_str("0.5"),
         "aleo184vuwr5u7u0ha5f5k44067dd2uaqewxx6pe5ltha5pv99wvhfqxqv339h4",
         "private",
         Some(fee_record.clone()),
-        100.00,
+        JsValue::from(100.0),
         Some(fee_record.clone()),
         Some("https://api.explorer.aleo.org/v1".to_string()),
         None,
@@ -217,6 +264,7 @@ async fn test_fee_validation() {
         None,
         None,
         None,
+        JsValue::UNDEFINED,
     )
     .await;
     assert!(transfer.is_err());
@@ -226,7 +274,7 @@ async fn test_fee_validation() {
         &private_key,
         fee_record.clone(),
         fee_record.clone(),
-        100.00,
+        JsValue::from(BigInt::from(100_000_000u64)),
         Some(fee_record.clone()),
         Some("https://api.explorer.aleo.org/v1".to_string()),
         None,
@@ -234,9 +282,47 @@ async fn test_fee_validation() {
         None,
         None,
         None,
+        JsValue::UNDEFINED,
     )
     .await;
     assert!(join.is_err());
+
+    // Ensure join fails when the fee record can pay the base fee but not the base and priority fee together
+    let join = ProgramManager::join(
+        &private_key,
+        fee_record.clone(),
+        fee_record.clone(),
+        JsValue::from_str("1.5"),
+        Some(fee_record.clone()),
+        Some("https://api.explorer.aleo.org/v1".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        JsValue::from(BigInt::from(1_000_000u64)),
+    )
+    .await;
+    let error = join.unwrap_err();
+    assert!(error.contains("Fee record does not have enough credits to pay 2500000 microcredits"), "{error}");
+
+    // Ensure fees which cannot be expressed in whole microcredits are rejected instead of truncated
+    let join = ProgramManager::join(
+        &private_key,
+        fee_record.clone(),
+        fee_record.clone(),
+        JsValue::from_str("0.0000001"),
+        Some(fee_record.clone()),
+        Some("https://api.explorer.aleo.org/v1".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        JsValue::UNDEFINED,
+    )
+    .await;
+    assert!(join.unwrap_err().contains("decimal places"));
 }
 
 #[wasm_bindgen_test]
@@ -255,6 +341,20 @@ async fn test_fee_estimation() {
     // Ensure the fee is greater a specific amount
     assert!(deployment_fee > 1940000);
 
+    // Ensure the itemized deployment cost adds up to the deployment fee
+    let breakdown = ProgramManager::estimate_deployment_cost(FINALIZE, None).await.unwrap();
+    let cost = |key: &str| Reflect::get(&breakdown, &JsValue::from_str(key)).unwrap().as_f64().unwrap() as u64;
+    assert_eq!(cost("totalCost"), deployment_fee);
+    assert_eq!(cost("storageCost") + cost("synthesisCost") + cost("namespaceCost"), deployment_fee);
+    let functions = Array::from(&Reflect::get(&breakdown, &JsValue::from_str("functions")).unwrap());
+    assert_eq!(functions.length(), 1);
+    let function = functions.get(0);
+    assert_eq!(
+        Reflect::get(&function, &JsValue::from_str("function")).unwrap().as_string().unwrap(),
+        "integer_key_mapping_update"
+    );
+    assert!(Reflect::get(&function, &JsValue::from_str("constraints")).unwrap().as_f64().unwrap() > 0.0);
+
     // Ensure the finalize fee is greater than zero for a program with a finalize scope
     let finalize_fee = ProgramManager::estimate_finalize_fee(FINALIZE, "integer_key_mapping_update").unwrap();
     assert!(finalize_fee > 0);
@@ -279,6 +379,112 @@ async fn test_fee_estimation() {
 
     // Ensure the total fee is greater than the finalize fee
     assert!(execution_fee > finalize_fee);
+
+    // Ensure the itemized execution cost attributes the finalize cost to the transition
+    let inputs = js_sys::Array::new_with_length(2);
+    inputs.set(0, wasm_bindgen::JsValue::from_str("15u64"));
+    inputs.set(1, wasm_bindgen::JsValue::from_str("15u64"));
+    let breakdown = ProgramManager::estimate_execution_cost(
+        &private_key,
+        FINALIZE,
+        "integer_key_mapping_update",
+        inputs,
+        Some("https://api.explorer.aleo.org/v1".to_string()),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let transitions = Array::from(&Reflect::get(&breakdown, &JsValue::from_str("transitions")).unwrap());
+    assert_eq!(transitions.length(), 1);
+    let transition_finalize_cost =
+        Reflect::get(&transitions.get(0), &JsValue::from_str("finalizeCost")).unwrap().as_f64().unwrap() as u64;
+    assert_eq!(transition_finalize_cost, finalize_fee);
+    let proven_bytes = Reflect::get(&breakdown, &JsValue::from_str("executionBytes")).unwrap().as_f64().unwrap() as u64;
+
+    // Ensure the cost can be estimated without proving the execution
+    let inputs = js_sys::Array::new_with_length(2);
+    inputs.set(0, wasm_bindgen::JsValue::from_str("15u64"));
+    inputs.set(1, wasm_bindgen::JsValue::from_str("15u64"));
+    let estimate = ProgramManager::estimate_execution_cost_without_proof(
+        &private_key,
+        FINALIZE,
+        "integer_key_mapping_update",
+        inputs,
+        None,
+    )
+    .unwrap();
+    let cost = |key: &str| Reflect::get(&estimate, &JsValue::from_str(key)).unwrap().as_f64().unwrap() as u64;
+    assert_eq!(cost("finalizeCost"), finalize_fee);
+    assert!(cost("totalCost") > finalize_fee);
+
+    // Ensure the estimated execution size does not fall below the size of the proven execution
+    assert_estimated_size(cost("executionBytes"), proven_bytes);
+
+    // Ensure the estimate covers the additional circuit of a call to an imported program
+    let imports = Object::new();
+    Reflect::set(&imports, &JsValue::from_str("multiply_test.aleo"), &JsValue::from_str(MULTIPLY_PROGRAM)).unwrap();
+    let inputs = Array::of1(&JsValue::from_str("5u32"));
+    let proven = ProgramManager::estimate_execution_cost(
+        &private_key,
+        MULTIPLY_IMPORT_PROGRAM,
+        "double_it",
+        inputs.clone(),
+        None,
+        Some(imports.clone()),
+        None,
+        None,
+        Some(Devnet::new().unwrap().to_offline_query().unwrap()),
+    )
+    .await
+    .unwrap();
+    let estimate = ProgramManager::estimate_execution_cost_without_proof(
+        &private_key,
+        MULTIPLY_IMPORT_PROGRAM,
+        "double_it",
+        inputs,
+        Some(imports),
+    )
+    .unwrap();
+    assert_estimated_size(execution_bytes(&estimate), execution_bytes(&proven));
+
+    // Ensure the estimate covers the inclusion circuit and its instances for consumed records
+    let mut devnet = funded_devnet(&private_key, false);
+    let record_1 = fund_record(&mut devnet, &private_key, 2_000_000).await;
+    let record_2 = fund_record(&mut devnet, &private_key, 3_000_000).await;
+    let credits = Program::get_credits_program().to_string();
+    let inputs = Array::of2(&JsValue::from_str(&record_1.to_string()), &JsValue::from_str(&record_2.to_string()));
+    let proven = ProgramManager::estimate_execution_cost(
+        &private_key,
+        &credits,
+        "join",
+        inputs.clone(),
+        None,
+        None,
+        None,
+        None,
+        Some(devnet.to_offline_query().unwrap()),
+    )
+    .await
+    .unwrap();
+    let estimate =
+        ProgramManager::estimate_execution_cost_without_proof(&private_key, &credits, "join", inputs, None).unwrap();
+    assert_estimated_size(execution_bytes(&estimate), execution_bytes(&proven));
+}
+
+// Read the size of the execution from an itemized execution cost.
+fn execution_bytes(breakdown: &Object) -> u64 {
+    Reflect::get(breakdown, &JsValue::from_str("executionBytes")).unwrap().as_f64().unwrap() as u64
+}
+
+// Assert that an estimated execution size is at least the proven size and at most 20% above it.
+fn assert_estimated_size(estimated_bytes: u64, proven_bytes: u64) {
+    assert!(
+        estimated_bytes >= proven_bytes && estimated_bytes * 5 <= proven_bytes * 6,
+        "estimated {estimated_bytes} bytes for an execution of {proven_bytes} bytes"
+    );
 }
 
 #[wasm_bindgen_test]
@@ -315,3 +521,698 @@ async fn test_import_resolution() {
     assert_eq!(outputs.len(), 1);
     assert_eq!(outputs[0], "30u32");
 }
+
+#[wasm_bindgen_test]
+async fn test_evaluate_function() {
+    let private_key = PrivateKey::new();
+    let inputs = js_sys::Array::new_with_length(2);
+    inputs.set(0, JsValue::from_f64(15.0));
+    inputs.set(1, JsValue::from_str("20u64"));
+
+    let result =
+        ProgramManager::evaluate_function_offline(&private_key, FINALIZE, "integer_key_mapping_update", inputs, None)
+            .await
+            .unwrap();
+
+    // Ensure the evaluation produced outputs without an execution or keys
+    assert!(result.get_execution().is_none());
+    assert!(result.get_verifying_key().is_err());
+
+    // Ensure the future output is decoded
+    let outputs = result.get_decoded_outputs().unwrap().to_vec();
+    assert_eq!(outputs.len(), 1);
+    let visibility = Reflect::get(&outputs[0], &JsValue::from_str("visibility")).unwrap();
+    assert_eq!(visibility.as_string().unwrap(), "future");
+    let future = Reflect::get(&outputs[0], &JsValue::from_str("value")).unwrap();
+    let function_name = Reflect::get(&future, &JsValue::from_str("functionName")).unwrap();
+    assert_eq!(function_name.as_string().unwrap(), "integer_key_mapping_update");
+    let arguments = Array::from(&Reflect::get(&future, &JsValue::from_str("arguments")).unwrap());
+    assert_eq!(arguments.length(), 2);
+}
+
+#[wasm_bindgen_test]
+async fn test_pinned_imports_are_checked_by_builders() {
+    let imports = Object::new();
+    Reflect::set(&imports, &JsValue::from_str("multiply_test.aleo"), &JsValue::from_str(MULTIPLY_PROGRAM)).unwrap();
+    let checksums = Object::new();
+    let outdated = MULTIPLY_PROGRAM.replace("mul r0 r1", "add r0 r1");
+    let outdated_checksum = Program::from_string(&outdated).unwrap().checksum().unwrap();
+    Reflect::set(&checksums, &JsValue::from_str("multiply_test.aleo"), &JsValue::from_str(&outdated_checksum)).unwrap();
+
+    // An import which differs from its pinned checksum is rejected before any keys are synthesized
+    let execution = ProgramManager::execute(
+        &PrivateKey::new(),
+        MULTIPLY_IMPORT_PROGRAM,
+        "double_it",
+        Array::of1(&JsValue::from_str("5u32")),
+        JsValue::from(BigInt::from(1_000_000u64)),
+        None,
+        None,
+        Some(imports.clone()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        JsValue::UNDEFINED,
+        Some(checksums.clone()),
+    )
+    .await;
+    assert!(execution.unwrap_err().contains("is pinned to checksum"));
+
+    let deployment = ProgramManager::deploy(
+        &PrivateKey::new(),
+        MULTIPLY_IMPORT_PROGRAM,
+        JsValue::from(BigInt::from(10_000_000u64)),
+        None,
+        None,
+        Some(imports),
+        None,
+        None,
+        None,
+        JsValue::UNDEFINED,
+        Some(checksums),
+    )
+    .await;
+    assert!(deployment.unwrap_err().contains("is pinned to checksum"));
+}
+
+#[wasm_bindgen_test]
+async fn test_program_manager_cache() {
+    let imports = Object::new();
+    Reflect::set(&imports, &JsValue::from_str("multiply_test.aleo"), &JsValue::from_str(MULTIPLY_PROGRAM)).unwrap();
+
+    let mut manager = ProgramManager::new().unwrap();
+    assert_eq!(manager.add_program(MULTIPLY_IMPORT_PROGRAM, Some(imports)).unwrap(), "double_test.aleo");
+    assert!(manager.has_program("multiply_test.aleo"));
+    assert_eq!(manager.get_programs().length(), 2);
+    assert!(manager.add_program(&MULTIPLY_PROGRAM.replace("mul r0 r1", "add r0 r1"), None).is_err());
+
+    // Keys synthesized by the first execution are reused by the second one
+    let private_key = PrivateKey::new();
+    let inputs = Array::of1(&JsValue::from_str("5u32"));
+    let result = manager.execute_cached(&private_key, "double_test.aleo", "double_it", &inputs, false, None, None);
+    assert_eq!(result.await.unwrap().get_outputs().to_vec()[0], "10u32");
+    let cached_keys = |manager: &mut ProgramManager| {
+        let usage = manager.memory_usage().unwrap();
+        Array::from(&Reflect::get(&usage, &JsValue::from_str("keys")).unwrap()).length()
+    };
+    assert_eq!(cached_keys(&mut manager), 2);
+    let mut keys = manager.synthesize_keys("double_test.aleo", "double_it").unwrap();
+    let result = manager.execute_cached(&private_key, "double_test.aleo", "double_it", &inputs, false, None, None);
+    assert_eq!(result.await.unwrap().get_outputs().to_vec()[0], "10u32");
+    assert_eq!(cached_keys(&mut manager), 2);
+
+    // Keys can be evicted and inserted again
+    manager.evict_keys("double_test.aleo", None).unwrap();
+    assert_eq!(cached_keys(&mut manager), 1);
+    manager
+        .insert_keys("double_test.aleo", "double_it", keys.proving_key().unwrap(), keys.verifying_key().unwrap())
+        .unwrap();
+    assert_eq!(cached_keys(&mut manager), 2);
+
+    // Evicting a program also evicts the programs importing it
+    manager.evict_program("multiply_test.aleo").unwrap();
+    assert!(!manager.has_program("multiply_test.aleo"));
+    assert!(!manager.has_program("double_test.aleo"));
+    assert_eq!(cached_keys(&mut manager), 0);
+}
+
+#[wasm_bindgen_test]
+async fn test_program_manager_async_execution() {
+    let mut manager = ProgramManager::new().unwrap();
+    manager.add_program(HELLO_PROGRAM, None).unwrap();
+    let cached_keys = |manager: &mut ProgramManager| {
+        let usage = manager.memory_usage().unwrap();
+        Array::from(&Reflect::get(&usage, &JsValue::from_str("keys")).unwrap()).length()
+    };
+    assert_eq!(cached_keys(&mut manager), 0);
+
+    // Keys synthesized by an execution running on a clone of the manager land in the instance
+    let inputs = Array::of2(&JsValue::from_str("5u32"), &JsValue::from_str("5u32"));
+    let promise = manager.execute_function(&PrivateKey::new(), "hello.aleo", "main", inputs, false, None, None);
+    JsFuture::from(promise).await.unwrap();
+    assert_eq!(cached_keys(&mut manager), 1);
+    let usage = manager.memory_usage().unwrap();
+    let key = Array::from(&Reflect::get(&usage, &JsValue::from_str("keys")).unwrap()).get(0);
+    assert_eq!(Reflect::get(&key, &JsValue::from_str("programId")).unwrap().as_string().unwrap(), "hello.aleo");
+    assert!(Reflect::get(&usage, &JsValue::from_str("provingKeyBytes")).unwrap().as_f64().unwrap() > 0.0);
+}
+
+#[wasm_bindgen_test]
+async fn test_program_manager_key_store() {
+    let imports = Object::new();
+    Reflect::set(&imports, &JsValue::from_str("multiply_test.aleo"), &JsValue::from_str(MULTIPLY_PROGRAM)).unwrap();
+    let key_store = KeyStore::in_memory();
+
+    // Keys synthesized by the first execution are persisted to the key store
+    let mut manager = ProgramManager::new().unwrap();
+    manager.set_key_store(&key_store);
+    manager.add_program(MULTIPLY_IMPORT_PROGRAM, Some(imports.clone())).unwrap();
+    let private_key = PrivateKey::new();
+    let inputs = Array::of1(&JsValue::from_str("5u32"));
+    let result = manager.execute_cached(&private_key, "double_test.aleo", "double_it", &inputs, false, None, None);
+    assert_eq!(result.await.unwrap().get_outputs().to_vec()[0], "10u32");
+    let mut keys = manager.synthesize_keys("multiply_test.aleo", "multiply").unwrap();
+
+    // A new program manager loads the persisted keys instead of synthesizing new ones
+    let mut restored = ProgramManager::new().unwrap();
+    restored.set_key_store(&key_store);
+    restored.add_program(MULTIPLY_IMPORT_PROGRAM, Some(imports)).unwrap();
+    let result = restored.execute_cached(&private_key, "double_test.aleo", "double_it", &inputs, false, None, None);
+    assert_eq!(result.await.unwrap().get_outputs().to_vec()[0], "10u32");
+    let mut restored_keys = restored.synthesize_keys("multiply_test.aleo", "multiply").unwrap();
+    assert_eq!(restored_keys.verifying_key().unwrap().checksum(), keys.verifying_key().unwrap().checksum());
+    assert_eq!(restored_keys.proving_key().unwrap().checksum(), keys.proving_key().unwrap().checksum());
+}
+
+// Create a javascript key storage backed by a Map.
+fn map_storage(entries: &Map) -> JsKeyStorage {
+    let storage = Function::new_with_args(
+        "entries",
+        "return { get: (key) => entries.get(key), set: (key, value) => { entries.set(key, value) }, remove: (key) => { entries.delete(key) } }",
+    );
+    storage.call1(&JsValue::NULL, entries).unwrap().unchecked_into()
+}
+
+// List the keys of the entries of a Map backed key storage.
+fn stored_entries(entries: &Map) -> Vec<String> {
+    let mut keys = Array::from(&entries.keys()).iter().map(|key| key.as_string().unwrap()).collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+#[wasm_bindgen_test]
+async fn test_static_builders_use_default_key_store() {
+    let private_key = PrivateKey::new();
+    let devnet = funded_devnet(&private_key, true);
+    let entries = Map::new();
+    KeyStore::new(map_storage(&entries)).set_default();
+
+    // Keys synthesized while building a transaction are persisted to the default key store
+    mint_transaction(&private_key, "5u64", devnet.to_offline_query().unwrap()).await;
+    let stored = stored_entries(&entries);
+    assert!(stored.iter().any(|key| key.starts_with("devnet_token.aleo/") && key.contains("/mint/prover.")));
+    assert!(stored.iter().any(|key| key.starts_with("credits.aleo/") && key.contains("/fee_public/prover.")));
+
+    // Later builds load the stored keys instead of synthesizing and persisting new ones
+    mint_transaction(&private_key, "5u64", devnet.to_offline_query().unwrap()).await;
+    assert_eq!(stored_entries(&entries), stored);
+    KeyStore::clear_default();
+}
+
+#[wasm_bindgen_test]
+async fn test_program_manager_key_plan() {
+    let imports = Object::new();
+    Reflect::set(&imports, &JsValue::from_str("multiply_test.aleo"), &JsValue::from_str(MULTIPLY_PROGRAM)).unwrap();
+    let mut manager = ProgramManager::new().unwrap();
+    manager.add_program(MULTIPLY_IMPORT_PROGRAM, Some(imports)).unwrap();
+
+    // The plan lists the function, the imported function it calls and the fee function
+    let plan = manager.plan_keys("double_test.aleo", "double_it", Some("public")).await.unwrap();
+    let locators = plan.iter().map(|key| format!("{}/{}", key.program_id, key.function_id)).collect::<Vec<_>>();
+    assert_eq!(locators, ["double_test.aleo/double_it", "multiply_test.aleo/multiply", "credits.aleo/fee_public"]);
+    assert!(plan.iter().all(|key| !key.cached && !key.stored));
+    assert!(!plan[0].published && plan[0].proving_key_bytes.is_none());
+    assert!(plan[2].published && plan[2].verifying_key_bytes.is_some());
+
+    let plan = manager.plan_keys("double_test.aleo", "double_it", Some("private")).await.unwrap();
+    assert_eq!(plan[2].function_id.to_string(), "fee_private");
+    assert_eq!(manager.plan_keys("double_test.aleo", "double_it", None).await.unwrap().len(), 2);
+    assert!(manager.plan_keys("double_test.aleo", "double_it", Some("free")).await.is_err());
+
+    // Keys synthesized by an execution are reported as cached along with their sizes
+    let inputs = Array::of1(&JsValue::from_str("5u32"));
+    let result =
+        manager.execute_cached(&PrivateKey::new(), "double_test.aleo", "double_it", &inputs, false, None, None);
+    assert_eq!(result.await.unwrap().get_outputs().to_vec()[0], "10u32");
+    let plan = manager.plan_keys("double_test.aleo", "double_it", None).await.unwrap();
+    assert!(plan.iter().all(|key| key.cached && key.proving_key_bytes.is_some() && key.verifying_key_bytes.is_some()));
+}
+
+#[wasm_bindgen_test]
+fn test_program_manager_program_keys() {
+    let imports = Object::new();
+    Reflect::set(&imports, &JsValue::from_str("multiply_test.aleo"), &JsValue::from_str(MULTIPLY_PROGRAM)).unwrap();
+    let mut manager = ProgramManager::new().unwrap();
+    manager.add_program(MULTIPLY_IMPORT_PROGRAM, Some(imports)).unwrap();
+
+    // Keys are synthesized for every function of the program and its imports without inputs
+    let keys = manager.synthesize_program_keys("double_test.aleo").unwrap();
+    assert_eq!(keys.functions().to_vec(), ["multiply_test.aleo/multiply", "double_test.aleo/double_it"]);
+    assert!(keys.keys().iter().all(|keys| keys.constraints > 0 && keys.variables > 0));
+    let counts = keys.constraint_counts().unwrap();
+    assert!(Reflect::has(&counts, &JsValue::from_str("double_test.aleo/double_it")).unwrap());
+
+    // The bundle round trips through bytes and matches the cached keys
+    let restored = ProgramKeys::from_bytes(&keys.to_bytes().unwrap()).unwrap();
+    assert_eq!(restored.functions().to_vec(), keys.functions().to_vec());
+    let mut cached = manager.synthesize_keys("multiply_test.aleo", "multiply").unwrap();
+    let mut bundled = restored.get_keys("multiply_test.aleo", "multiply").unwrap();
+    assert_eq!(bundled.verifying_key().unwrap().checksum(), cached.verifying_key().unwrap().checksum());
+    assert_eq!(bundled.proving_key().unwrap().checksum(), cached.proving_key().unwrap().checksum());
+    assert!(manager.synthesize_program_keys("missing.aleo").is_err());
+
+    // Every function is encoded as a key bundle, so corrupted keys are rejected by their checksum
+    let mut corrupted = keys.to_bytes().unwrap();
+    *corrupted.last_mut().unwrap() ^= 1;
+    assert!(ProgramKeys::from_bytes(&corrupted).unwrap_err().contains("checksum"));
+}
+
+#[wasm_bindgen_test]
+fn test_verify_deployed_key() {
+    let mut manager = ProgramManager::new().unwrap();
+    manager.add_program(HELLO_PROGRAM, None).unwrap();
+    let deployed_key = manager.synthesize_keys("hello.aleo", "main").unwrap().verifying_key().unwrap().to_string();
+    let identical = |result: &Object| Reflect::get(result, &JsValue::from_str("identical")).unwrap().as_bool().unwrap();
+
+    // Verifying keys are synthesized deterministically, so the same source yields the deployed key
+    let result = ProgramManager::verify_deployed_key(HELLO_PROGRAM, "main", &deployed_key, None).unwrap();
+    assert!(identical(&result));
+    assert!(Reflect::get(&result, &JsValue::from_str("programMatches")).unwrap().is_undefined());
+
+    // A different source for the same program id yields a different key
+    let edited = HELLO_PROGRAM.replace("add r0 r1", "add.w r0 r1");
+    let result = ProgramManager::verify_deployed_key(&edited, "main", &deployed_key, None).unwrap();
+    assert!(!identical(&result));
+
+    assert!(ProgramManager::verify_deployed_key(HELLO_PROGRAM, "hello", &deployed_key, None).is_err());
+    assert!(ProgramManager::verify_deployed_key(HELLO_PROGRAM, "main", "not a key", None).is_err());
+}
+
+// Create a devnet containing the token program and an account funded with 100 credits.
+fn funded_devnet(private_key: &PrivateKey, deploy_token: bool) -> Devnet {
+    let mut devnet = Devnet::new().unwrap();
+    if deploy_token {
+        devnet.add_program(TOKEN_PROGRAM).unwrap();
+    }
+    let address = private_key.to_address().to_string();
+    devnet.set_mapping_value("credits.aleo", "account", &address, "100000000u64").unwrap();
+    devnet
+}
+
+// Build a transaction minting tokens, paying the fee publicly.
+async fn mint_transaction(private_key: &PrivateKey, amount: &str, offline_query: OfflineQuery) -> Transaction {
+    ProgramManager::execute(
+        private_key,
+        TOKEN_PROGRAM,
+        "mint",
+        Array::of1(&JsValue::from_str(amount)),
+        JsValue::from(BigInt::from(1_000_000u64)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(offline_query),
+        JsValue::UNDEFINED,
+        None,
+    )
+    .await
+    .unwrap()
+}
+
+// Build a transaction deploying the token program, paying the fee publicly.
+async fn deploy_transaction(private_key: &PrivateKey, offline_query: OfflineQuery) -> Transaction {
+    ProgramManager::deploy(
+        private_key,
+        TOKEN_PROGRAM,
+        JsValue::from(BigInt::from(20_000_000u64)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(offline_query),
+        JsValue::UNDEFINED,
+        None,
+    )
+    .await
+    .unwrap()
+}
+
+// Decrypt the records output by a transaction (including its fee transition) owned by an account.
+fn owned_records(transaction: &Transaction, private_key: &PrivateKey) -> Vec<RecordPlaintext> {
+    let transaction: serde_json::Value = serde_json::from_str(&transaction.to_string()).unwrap();
+    let mut transitions = transaction["execution"]["transitions"].as_array().cloned().unwrap_or_default();
+    transitions.push(transaction["fee"]["transition"].clone());
+    let view_key = private_key.to_view_key();
+    transitions
+        .iter()
+        .filter_map(|transition| transition["outputs"].as_array())
+        .flatten()
+        .filter(|output| output["type"] == "record")
+        .map(|output| RecordCiphertext::from_string(output["value"].as_str().unwrap()).unwrap())
+        .filter(|record| record.is_owner(&view_key))
+        .map(|record| record.decrypt(&view_key).unwrap())
+        .collect()
+}
+
+// Submit a transaction to the devnet and assert that it was accepted.
+fn submit_accepted(devnet: &mut Devnet, transaction: &Transaction) {
+    let result = devnet.submit_transaction(transaction).unwrap();
+    let accepted = Reflect::get(&result, &JsValue::from_str("accepted")).unwrap().as_bool().unwrap();
+    assert!(accepted, "{:?}", Reflect::get(&result, &JsValue::from_str("error")).unwrap());
+}
+
+// Fund a credits record from the public balance of an account, paying the fee publicly.
+async fn fund_record(devnet: &mut Devnet, private_key: &PrivateKey, microcredits: u64) -> RecordPlaintext {
+    let address = private_key.to_address().to_string();
+    let transaction = ProgramManager::transfer(
+        private_key,
+        JsValue::from(BigInt::from(microcredits)),
+        &address,
+        "public_to_private",
+        None,
+        JsValue::from(BigInt::from(1_000_000u64)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(devnet.to_offline_query().unwrap()),
+        JsValue::UNDEFINED,
+    )
+    .await
+    .unwrap();
+    submit_accepted(devnet, &transaction);
+    owned_records(&transaction, private_key).pop().unwrap()
+}
+
+// Build a private transfer of a record to its owner, paying the fee publicly.
+async fn transfer_private_transaction(
+    private_key: &PrivateKey,
+    record: RecordPlaintext,
+    microcredits: u64,
+    offline_query: OfflineQuery,
+) -> Transaction {
+    let address = private_key.to_address().to_string();
+    ProgramManager::transfer(
+        private_key,
+        JsValue::from(BigInt::from(microcredits)),
+        &address,
+        "private",
+        Some(record),
+        JsValue::from(BigInt::from(1_000_000u64)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(offline_query),
+        JsValue::UNDEFINED,
+    )
+    .await
+    .unwrap()
+}
+
+// Count the state paths of an offline query.
+fn state_path_count(offline_query: &OfflineQuery) -> u32 {
+    let offline_query = JSON::parse(&offline_query.to_string()).unwrap();
+    Object::keys(&Object::from(Reflect::get(&offline_query, &JsValue::from_str("state_paths")).unwrap())).length()
+}
+
+#[wasm_bindgen_test]
+async fn test_devnet_accepted_execution() {
+    let private_key = PrivateKey::new();
+    let mut devnet = funded_devnet(&private_key, true);
+
+    let transaction = mint_transaction(&private_key, "5u64", devnet.to_offline_query().unwrap()).await;
+    let result = devnet.submit_transaction(&transaction).unwrap();
+    assert!(Reflect::get(&result, &JsValue::from_str("accepted")).unwrap().as_bool().unwrap());
+    assert_eq!(Reflect::get(&result, &JsValue::from_str("height")).unwrap().as_f64().unwrap(), 0.0);
+    assert_eq!(devnet.get_mapping_value("devnet_token.aleo", "supply", "true").unwrap(), Some("5u64".to_string()));
+
+    // The commitment of the minted record is tracked, so the offline query can prove its inclusion
+    let offline_query = devnet.to_offline_query().unwrap();
+    assert_eq!(state_path_count(&offline_query), 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_devnet_rejected_execution() {
+    let private_key = PrivateKey::new();
+    let mut devnet = funded_devnet(&private_key, true);
+
+    // Minting more than the finalize logic allows is rejected and only the fee is applied
+    let transaction = mint_transaction(&private_key, "500u64", devnet.to_offline_query().unwrap()).await;
+    let result = devnet.submit_transaction(&transaction).unwrap();
+    assert!(!Reflect::get(&result, &JsValue::from_str("accepted")).unwrap().as_bool().unwrap());
+    assert_eq!(devnet.get_mapping_value("devnet_token.aleo", "supply", "true").unwrap(), None);
+    let address = private_key.to_address().to_string();
+    assert_ne!(
+        devnet.get_mapping_value("credits.aleo", "account", &address).unwrap(),
+        Some("100000000u64".to_string())
+    );
+
+    // The outputs of the rejected execution are not part of the devnet state
+    let offline_query = devnet.to_offline_query().unwrap();
+    assert_eq!(state_path_count(&offline_query), 0);
+
+    // Later transactions can still be built and submitted against the devnet state
+    let transaction = mint_transaction(&private_key, "5u64", offline_query).await;
+    let result = devnet.submit_transaction(&transaction).unwrap();
+    assert!(Reflect::get(&result, &JsValue::from_str("accepted")).unwrap().as_bool().unwrap());
+    assert_eq!(state_path_count(&devnet.to_offline_query().unwrap()), 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_devnet_transfer_private() {
+    let private_key = PrivateKey::new();
+    let mut devnet = funded_devnet(&private_key, false);
+    let record = fund_record(&mut devnet, &private_key, 5_000_000).await;
+
+    let transaction =
+        transfer_private_transaction(&private_key, record.clone(), 2_000_000, devnet.to_offline_query().unwrap()).await;
+    submit_accepted(&mut devnet, &transaction);
+    let mut balances =
+        owned_records(&transaction, &private_key).iter().map(|record| record.microcredits()).collect::<Vec<_>>();
+    balances.sort();
+    assert_eq!(balances, vec![2_000_000, 3_000_000]);
+
+    // The spent record cannot be spent again
+    let transaction =
+        transfer_private_transaction(&private_key, record, 1_000_000, devnet.to_offline_query().unwrap()).await;
+    assert!(devnet.submit_transaction(&transaction).unwrap_err().contains("already spent"));
+}
+
+#[wasm_bindgen_test]
+async fn test_devnet_join() {
+    let private_key = PrivateKey::new();
+    let mut devnet = funded_devnet(&private_key, false);
+    let record_1 = fund_record(&mut devnet, &private_key, 2_000_000).await;
+    let record_2 = fund_record(&mut devnet, &private_key, 3_000_000).await;
+
+    let transaction = ProgramManager::join(
+        &private_key,
+        record_1,
+        record_2,
+        JsValue::from(BigInt::from(1_000_000u64)),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(devnet.to_offline_query().unwrap()),
+        JsValue::UNDEFINED,
+    )
+    .await
+    .unwrap();
+    submit_accepted(&mut devnet, &transaction);
+    let records = owned_records(&transaction, &private_key);
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].microcredits(), 5_000_000);
+}
+
+#[wasm_bindgen_test]
+async fn test_devnet_split() {
+    let private_key = PrivateKey::new();
+    let mut devnet = funded_devnet(&private_key, false);
+    let record = fund_record(&mut devnet, &private_key, 5_000_000).await;
+
+    let transaction = ProgramManager::split(
+        &private_key,
+        JsValue::from(BigInt::from(2_000_000u64)),
+        record,
+        None,
+        None,
+        None,
+        Some(devnet.to_offline_query().unwrap()),
+    )
+    .await
+    .unwrap();
+    submit_accepted(&mut devnet, &transaction);
+    let mut balances =
+        owned_records(&transaction, &private_key).iter().map(|record| record.microcredits()).collect::<Vec<_>>();
+    balances.sort();
+    // The split program deducts a fixed fee of 10_000 microcredits from the split record
+    assert_eq!(balances, vec![2_000_000, 2_990_000]);
+}
+
+#[wasm_bindgen_test]
+async fn test_devnet_private_fee() {
+    let private_key = PrivateKey::new();
+    let mut devnet = funded_devnet(&private_key, true);
+    let fee_record = fund_record(&mut devnet, &private_key, 5_000_000).await;
+
+    let transaction = ProgramManager::execute(
+        &private_key,
+        TOKEN_PROGRAM,
+        "mint",
+        Array::of1(&JsValue::from_str("5u64")),
+        JsValue::from(BigInt::from(1_000_000u64)),
+        Some(fee_record),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(devnet.to_offline_query().unwrap()),
+        JsValue::UNDEFINED,
+        None,
+    )
+    .await
+    .unwrap();
+    submit_accepted(&mut devnet, &transaction);
+    assert_eq!(devnet.get_mapping_value("devnet_token.aleo", "supply", "true").unwrap(), Some("5u64".to_string()));
+
+    // The change of the fee record is returned by the fee transition
+    let change = owned_records(&transaction, &private_key)
+        .into_iter()
+        .filter(|record| record.microcredits() == 4_000_000)
+        .count();
+    assert_eq!(change, 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_devnet_deployment() {
+    let private_key = PrivateKey::new();
+    let mut devnet = funded_devnet(&private_key, false);
+
+    let transaction = deploy_transaction(&private_key, devnet.to_offline_query().unwrap()).await;
+    let result = devnet.submit_transaction(&transaction).unwrap();
+    assert!(Reflect::get(&result, &JsValue::from_str("accepted")).unwrap().as_bool().unwrap());
+    assert_eq!(devnet.latest_height(), Some(0));
+    assert!(devnet.get_program("devnet_token.aleo").is_ok());
+    assert_eq!(devnet.get_mapping_value("devnet_token.aleo", "supply", "true").unwrap(), None);
+
+    // A program cannot be deployed twice, and the deployed program can be executed
+    let transaction = deploy_transaction(&private_key, devnet.to_offline_query().unwrap()).await;
+    assert!(devnet.submit_transaction(&transaction).unwrap_err().contains("already deployed"));
+    let transaction = mint_transaction(&private_key, "5u64", devnet.to_offline_query().unwrap()).await;
+    devnet.submit_transaction(&transaction).unwrap();
+    assert_eq!(devnet.latest_height(), Some(1));
+    assert_eq!(devnet.get_mapping_value("devnet_token.aleo", "supply", "true").unwrap(), Some("5u64".to_string()));
+}
+
+// Prove an execution minting tokens without a fee.
+async fn mint_execution(private_key: &PrivateKey, amount: &str) -> Execution {
+    let offline_query = Devnet::new().unwrap().to_offline_query().unwrap();
+    let response = ProgramManager::execute_function_offline(
+        private_key,
+        TOKEN_PROGRAM,
+        "mint",
+        Array::of1(&JsValue::from_str(amount)),
+        true,
+        false,
+        None,
+        None,
+        None,
+        None,
+        Some(offline_query),
+    )
+    .await
+    .unwrap();
+    response.get_execution().unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_simulate_finalize() {
+    let private_key = PrivateKey::new();
+    let mut store = MappingStore::new().unwrap();
+    store.set_value("devnet_token.aleo", "supply", "true", "10u64").unwrap();
+    let get = |object: &JsValue, key: &str| Reflect::get(object, &JsValue::from_str(key)).unwrap();
+
+    // An accepted execution updates the store and reports the value of each changed entry before and after finalize
+    let execution = mint_execution(&private_key, "5u64").await;
+    let result = ProgramManager::simulate_finalize(&mut store, &execution, TOKEN_PROGRAM, None, None).unwrap();
+    assert_eq!(get(&result, "accepted").as_bool(), Some(true));
+    assert!(get(&result, "error").is_null());
+    let diffs = Array::from(&get(&result, "diffs"));
+    assert_eq!(diffs.length(), 1);
+    let diff = diffs.get(0);
+    assert_eq!(get(&diff, "program").as_string().unwrap(), "devnet_token.aleo");
+    assert_eq!(get(&diff, "mapping").as_string().unwrap(), "supply");
+    assert_eq!(get(&diff, "key").as_string().unwrap(), "true");
+    assert_eq!(get(&diff, "before").as_string().unwrap(), "10u64");
+    assert_eq!(get(&diff, "after").as_string().unwrap(), "15u64");
+    assert_eq!(store.get_value("devnet_token.aleo", "supply", "true").unwrap(), Some("15u64".to_string()));
+
+    // An execution whose finalize fails is rejected without diffs and leaves the store unchanged
+    let execution = mint_execution(&private_key, "500u64").await;
+    let result = ProgramManager::simulate_finalize(&mut store, &execution, TOKEN_PROGRAM, None, None).unwrap();
+    assert_eq!(get(&result, "accepted").as_bool(), Some(false));
+    assert!(get(&result, "error").is_string());
+    assert_eq!(Array::from(&get(&result, "diffs")).length(), 0);
+    assert_eq!(store.get_value("devnet_token.aleo", "supply", "true").unwrap(), Some("15u64".to_string()));
+    let mapping = store.get_mapping("devnet_token.aleo", "supply").unwrap();
+    assert_eq!(Object::keys(&mapping).length(), 1);
+}
+
+#[wasm_bindgen_test]
+async fn test_verify_deployed_key_from_transaction() {
+    let private_key = PrivateKey::new();
+    let devnet = funded_devnet(&private_key, false);
+    let deployment = deploy_transaction(&private_key, devnet.to_offline_query().unwrap()).await.to_string();
+    let flag = |result: &Object, name: &str| Reflect::get(result, &JsValue::from_str(name)).unwrap().as_bool().unwrap();
+
+    // The key deployed for a function matches the key synthesized from the deployed source
+    let result = ProgramManager::verify_deployed_key(TOKEN_PROGRAM, "mint", &deployment, None).unwrap();
+    assert!(flag(&result, "identical"));
+    assert!(flag(&result, "programMatches"));
+
+    // A source differing from the deployed program is reported, and its new functions have no deployed key
+    let extended = format!(
+        "{TOKEN_PROGRAM}\nfunction burn:\n    input r0 as u64.public;\n    add r0 r0 into r1;\n    output r1 as u64.public;\n"
+    );
+    let result = ProgramManager::verify_deployed_key(&extended, "mint", &deployment, None).unwrap();
+    assert!(!flag(&result, "programMatches"));
+    let error = ProgramManager::verify_deployed_key(&extended, "burn", &deployment, None).unwrap_err();
+    assert!(error.contains("has no verifying key for burn"), "{error}");
+
+    // Deployments of another program are rejected
+    let error = ProgramManager::verify_deployed_key(HELLO_PROGRAM, "main", &deployment, None).unwrap_err();
+    assert!(error.contains("deploys devnet_token.aleo, not hello.aleo"), "{error}");
+}
+
+#[wasm_bindgen_test]
+async fn test_record_spent_in_transaction() {
+    let private_key = PrivateKey::new();
+    let mut devnet = funded_devnet(&private_key, false);
+    let spent = fund_record(&mut devnet, &private_key, 5_000_000).await;
+    let unspent = fund_record(&mut devnet, &private_key, 5_000_000).await;
+
+    let transaction =
+        transfer_private_transaction(&private_key, spent.clone(), 1_000_000, devnet.to_offline_query().unwrap()).await;
+    let graph_key = GraphKey::from_private_key(&private_key).unwrap();
+    assert!(spent.is_spent_in(&graph_key, "credits.aleo", "credits", &transaction).unwrap());
+    assert!(!unspent.is_spent_in(&graph_key, "credits.aleo", "credits", &transaction).unwrap());
+
+    // The transaction spends exactly the record whose tag it contains, whichever key derived the graph key
+    let graph_key = GraphKey::from_view_key(&private_key.to_view_key()).unwrap();
+    let tag = spent.tag_string(&graph_key, "credits.aleo", "credits").unwrap();
+    let unspent_tag = unspent.tag_string(&graph_key, "credits.aleo", "credits").unwrap();
+    assert!(transaction.has_record_tag(&tag).unwrap());
+    assert!(!transaction.has_record_tag(&unspent_tag).unwrap());
+    assert_eq!(transaction.record_tags().to_vec(), vec![JsValue::from_str(&tag)]);
+
+    // The records output by the transfer are not spent by it
+    for record in owned_records(&transaction, &private_key) {
+        assert!(!record.is_spent_in(&graph_key, "credits.aleo", "credits", &transaction).unwrap());
+    }
+}